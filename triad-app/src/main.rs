@@ -3,15 +3,19 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use std::time::Instant;
 
+use glam::{Mat4, Vec3};
 use tracing::{error, info};
 use triad_gpu::{
-    BindingType, BufferUsage, ComputePassBuilder, CopyPassBuilder, DepthLoadOp,
-    DispatchIndirectArgs, DrawIndirectArgs, ExecutableFrameGraph, FrameBufferHandle, FrameGraph,
-    FrameGraphError, FrameTextureView, Handle, Pass, PassBuilder, PassContext, RenderPassBuilder,
-    Renderer, ResourceRegistry, ShaderStage, SpatialGridConfig, SpatialGridGpu, SpatialGridParams,
-    total_cells, wgpu,
+    BindingType, BufferUsage, ComputePassBuilder, CopyPassBuilder, DebugLineRenderer, DebugLines,
+    DepthLoadOp, DepthMode, DispatchIndirectArgs, Download, DrawIndirectArgs, ExecutableFrameGraph,
+    FrameGraph, FrameGraphError, FrameTextureView, Handle, Pass, PassBuilder, PassContext,
+    RenderPassBuilder, Renderer, ResourceRegistry, ShaderStage, SpatialGridConfig, SpatialGridGpu,
+    SpatialGridParams, total_cells, wgpu, workgroup_count,
+};
+use triad_window::{
+    CameraUniforms, KeyBindings, KeyCode, RendererConfig, RendererManager, WindowConfig, egui,
+    run_with_renderer_config,
 };
-use triad_window::{CameraUniforms, RendererManager, WindowConfig, egui, run_with_renderer_config};
 
 const DEFAULT_PARTICLE_COUNT: usize = 4_096;
 const MIN_PARTICLE_COUNT: usize = 256;
@@ -22,6 +26,10 @@ const PARTICLE_RADIUS: f32 = 0.00875;
 /// Jacobi-style separation passes per frame (same spatial hash each pass).
 /// Lower this if GPU-bound; 2 is usually enough with the merged neighbor loop.
 const COLLISION_ITERATIONS: u32 = 2;
+/// Range and keyboard step for the render-only particle size multiplier.
+const MIN_POINT_SCALE: f32 = 0.25;
+const MAX_POINT_SCALE: f32 = 8.0;
+const POINT_SCALE_STEP: f32 = 1.25;
 
 /// Uniform 2D grid over simulation xy ∈ [-1, 1]; single z slab. Keeps `total_cells` moderate for the scan.
 const SPATIAL_GRID_DIMS: [u32; 3] = [64, 64, 1];
@@ -292,7 +300,6 @@ fn particle_count_from_env() -> usize {
     n
 }
 const READBACK_INTERVAL_FRAMES: u64 = 15;
-const READBACK_RING_SIZE: usize = 3;
 const READBACK_VALIDATE_INTERVAL_FRAMES: u64 = 240;
 
 fn grid_neighbor_validate_from_env() -> bool {
@@ -301,6 +308,26 @@ fn grid_neighbor_validate_from_env() -> bool {
         .unwrap_or(false)
 }
 
+const ACTION_POINT_SCALE_UP: &str = "point_scale_up";
+const ACTION_POINT_SCALE_DOWN: &str = "point_scale_down";
+
+/// Window defaults plus the demo's own actions, overridden by the TOML file at
+/// `TRIAD_KEYBINDINGS` when set.
+fn key_bindings_from_env() -> KeyBindings {
+    let mut bindings = KeyBindings::default()
+        .with_binding(ACTION_POINT_SCALE_UP, [KeyCode::BracketRight])
+        .with_binding(ACTION_POINT_SCALE_DOWN, [KeyCode::BracketLeft]);
+    if let Ok(path) = std::env::var("TRIAD_KEYBINDINGS") {
+        match KeyBindings::load(&path) {
+            Ok(overrides) => {
+                bindings.merge(overrides);
+            }
+            Err(err) => tracing::warn!(path, error = %err, "ignoring TRIAD_KEYBINDINGS"),
+        }
+    }
+    bindings
+}
+
 const RESET_DRAW_ARGS_SHADER: &str = r#"
 struct DrawArgs {
     vertex_count: u32,
@@ -444,7 +471,8 @@ struct VisibleIds {
 /// width / height; maps simulation xy to NDC so one sim unit is isotropic in pixels (letterboxed).
 struct SimViewParams {
     aspect: f32,
-    _pad0: f32,
+    /// Render-only multiplier on the disk radius; collision keeps `PARTICLE_RADIUS`.
+    point_scale: f32,
     _pad1: f32,
     _pad2: f32,
 }
@@ -453,6 +481,14 @@ struct SimViewParams {
 @group(0) @binding(1) var<storage, read> visible: VisibleIds;
 @group(0) @binding(2) var<uniform> sim_view: SimViewParams;
 
+struct SceneEnvironment {
+    ambient_tint: vec4<f32>,
+    /// x = brightness, y = contrast, z = saturation, w = temperature (-1 cool .. 1 warm).
+    grading: vec4<f32>,
+}
+
+@group(0) @binding(3) var<uniform> environment: SceneEnvironment;
+
 // Must match [`PARTICLE_RADIUS`] in Rust (same world units).
 const QUAD_HALF: f32 = 0.00875;
 
@@ -469,6 +505,7 @@ fn vs_main(
     let particle_id = visible.ids[instance_index];
     let particle = particles.particles[particle_id];
 
+    // Layer particles by id so overlapping disks resolve consistently.
     let n = max(arrayLength(&particles.particles), 1u);
     let z = 0.05 + 0.9 * (f32(particle_id) / f32(n));
 
@@ -477,7 +514,7 @@ fn vs_main(
         vec2(-1.0, -1.0), vec2(1.0, 1.0), vec2(-1.0, 1.0)
     );
     let q = corners[vertex_index];
-    let world = particle.position + q * QUAD_HALF;
+    let world = particle.position + q * QUAD_HALF * sim_view.point_scale;
 
     let a = sim_view.aspect;
     var ndc_xy: vec2<f32>;
@@ -498,7 +535,42 @@ fn fs_main(@location(0) corner: vec2<f32>) -> @location(0) vec4<f32> {
     if (length(corner) > 1.0) {
         discard;
     }
-    return vec4<f32>(0.95, 0.75, 0.2, 1.0);
+    let base = vec3<f32>(0.95, 0.75, 0.2) * environment.ambient_tint.rgb;
+    return vec4<f32>(base, 1.0);
+}
+"#;
+
+/// Fullscreen pass that grades the offscreen scene color into the surface.
+const COLOR_GRADE_SHADER: &str = r#"
+struct SceneEnvironment {
+    ambient_tint: vec4<f32>,
+    /// x = brightness, y = contrast, z = saturation, w = temperature (-1 cool .. 1 warm).
+    grading: vec4<f32>,
+}
+
+@group(0) @binding(0) var scene_color: texture_2d<f32>;
+@group(0) @binding(1) var<uniform> environment: SceneEnvironment;
+
+fn color_grade(color: vec3<f32>) -> vec3<f32> {
+    let g = environment.grading;
+    var c = color * vec3<f32>(1.0 + 0.2 * g.w, 1.0, 1.0 - 0.2 * g.w);
+    c = c + g.x;
+    c = (c - 0.5) * g.y + 0.5;
+    let luma = dot(c, vec3<f32>(0.2126, 0.7152, 0.0722));
+    c = mix(vec3<f32>(luma), c, g.z);
+    return clamp(c, vec3<f32>(0.0), vec3<f32>(1.0));
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> @builtin(position) vec4<f32> {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    return vec4<f32>(uv * 2.0 - 1.0, 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) frag_pos: vec4<f32>) -> @location(0) vec4<f32> {
+    let color = textureLoad(scene_color, vec2<i32>(frag_pos.xy), 0);
+    return vec4<f32>(color_grade(color.rgb), color.a);
 }
 "#;
 
@@ -547,16 +619,241 @@ struct SimParams {
 struct SimViewParams {
     /// `viewport_width / viewport_height` — keeps simulation units isotropic on screen.
     aspect: f32,
-    _pad: [f32; 3],
+    /// Render-only disk radius multiplier (see [`SceneEnvironment::point_scale`]).
+    point_scale: f32,
+    _pad: [f32; 2],
+}
+
+/// Live color adjustments applied to the whole frame by the color-grade pass.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct ColorGrading {
+    /// Added to each channel (-0.5..0.5).
+    brightness: f32,
+    /// Scales distance from mid-grey; 1 is neutral.
+    contrast: f32,
+    /// 0 is greyscale, 1 is neutral.
+    saturation: f32,
+    /// White balance shift: negative cools (blue), positive warms (red).
+    temperature: f32,
+}
+
+impl Default for ColorGrading {
+    fn default() -> Self {
+        Self {
+            brightness: 0.0,
+            contrast: 1.0,
+            saturation: 1.0,
+            temperature: 0.0,
+        }
+    }
+}
+
+/// Runtime-editable look of the scene: clear color and an ambient tint on particles.
+///
+/// The demo is 2D with no view depth, so unlike the visualizer it has no fog.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SceneEnvironment {
+    clear_color: [f32; 3],
+    ambient_tint: [f32; 3],
+    /// Multiplier on the drawn particle size; does not affect collisions.
+    point_scale: f32,
+    grading: ColorGrading,
+    /// Overlay the simulation bounds, spatial grid cells and axes.
+    debug_geometry: bool,
+}
+
+impl Default for SceneEnvironment {
+    fn default() -> Self {
+        Self {
+            clear_color: [0.04, 0.05, 0.06],
+            ambient_tint: [1.0, 1.0, 1.0],
+            point_scale: 1.0,
+            grading: ColorGrading::default(),
+            debug_geometry: false,
+        }
+    }
+}
+
+impl SceneEnvironment {
+    fn params(&self) -> SceneEnvironmentParams {
+        let [ar, ag, ab] = self.ambient_tint;
+        SceneEnvironmentParams {
+            ambient_tint: [ar, ag, ab, 1.0],
+            grading: [
+                self.grading.brightness,
+                self.grading.contrast,
+                self.grading.saturation,
+                self.grading.temperature,
+            ],
+        }
+    }
+
+    fn set_point_scale(&mut self, scale: f32) {
+        self.point_scale = scale.clamp(MIN_POINT_SCALE, MAX_POINT_SCALE);
+    }
+
+    fn clear_color(&self) -> wgpu::Color {
+        let [r, g, b] = self.clear_color;
+        wgpu::Color {
+            r: r as f64,
+            g: g as f64,
+            b: b as f64,
+            a: 1.0,
+        }
+    }
 }
 
-fn sim_view_params_from_viewport(viewport_w: u32, viewport_h: u32) -> SimViewParams {
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SceneEnvironmentParams {
+    ambient_tint: [f32; 4],
+    /// brightness, contrast, saturation, temperature.
+    grading: [f32; 4],
+}
+
+/// Offscreen color target the scene draws into. The color-grade pass reads it
+/// and writes the graded frame to the surface; recreated when the viewport resizes.
+struct SceneColorTarget {
+    texture: Handle<wgpu::Texture>,
+    view: Handle<wgpu::TextureView>,
+    layout: Handle<wgpu::BindGroupLayout>,
+    bind_group: Handle<wgpu::BindGroup>,
+    width: u32,
+    height: u32,
+}
+
+impl SceneColorTarget {
+    /// Create the target and its grade bind group, and point `slot` at the new view.
+    fn new(
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        slot: Handle<FrameTextureView>,
+        format: wgpu::TextureFormat,
+        environment_buffer: Handle<wgpu::Buffer>,
+        width: u32,
+        height: u32,
+    ) -> Result<Self, Box<dyn Error>> {
+        let texture = renderer
+            .create_texture()
+            .label("scene color")
+            .size_2d(width, height)
+            .format(format)
+            .usage_flags(
+                wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            )
+            .build(registry)?;
+        let view = renderer
+            .create_texture_view(texture)
+            .label("scene color view")
+            .build(registry)?;
+        let (layout, bind_group) = renderer
+            .create_bind_group()
+            .label("color grade")
+            .texture_stage(
+                0,
+                ShaderStage::Fragment,
+                view,
+                BindingType::Texture {
+                    sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                    view_dimension: wgpu::TextureViewDimension::D2,
+                    multisampled: false,
+                },
+            )
+            .buffer_stage(
+                1,
+                ShaderStage::Fragment,
+                environment_buffer,
+                BindingType::Uniform,
+            )
+            .build(registry)?;
+        let frame_view = registry
+            .get(view)
+            .cloned()
+            .expect("scene color view should exist");
+        registry
+            .get(slot)
+            .expect("scene color slot should exist")
+            .set(Arc::new(frame_view));
+        Ok(Self {
+            texture,
+            view,
+            layout,
+            bind_group,
+            width,
+            height,
+        })
+    }
+
+    fn release(self, registry: &mut ResourceRegistry) {
+        registry.remove(self.bind_group);
+        registry.remove(self.layout);
+        registry.remove(self.view);
+        registry.remove(self.texture);
+    }
+}
+
+fn sim_view_params_from_viewport(
+    viewport_w: u32,
+    viewport_h: u32,
+    point_scale: f32,
+) -> SimViewParams {
     let w = viewport_w.max(1) as f32;
     let h = viewport_h.max(1) as f32;
     SimViewParams {
         aspect: w / h,
-        _pad: [0.0; 3],
+        point_scale,
+        _pad: [0.0; 2],
+    }
+}
+
+/// Maps simulation xy to NDC the same way the particle vertex shader does.
+fn sim_view_proj(viewport_w: u32, viewport_h: u32) -> Mat4 {
+    let aspect = viewport_w.max(1) as f32 / viewport_h.max(1) as f32;
+    let scale = if aspect >= 1.0 {
+        Vec3::new(1.0 / aspect, 1.0, 1.0)
+    } else {
+        Vec3::new(1.0, aspect, 1.0)
+    };
+    Mat4::from_scale(scale)
+}
+
+/// Simulation bounds, spatial grid cell boundaries and the xy axes.
+fn sim_debug_lines(grid: &SpatialGridParams) -> DebugLines {
+    let mut lines = DebugLines::new();
+    let [ox, oy, _, cell] = grid.world_origin_cell;
+    let origin = Vec3::new(ox, oy, 0.0);
+    let [nx, ny, _, _] = grid.grid_dims_entities;
+    let extent = Vec3::new(nx as f32 * cell, ny as f32 * cell, 0.0);
+    let cell_color = [0.35, 0.4, 0.5, 0.25];
+    for i in 1..nx {
+        let x = origin.x + i as f32 * cell;
+        lines.line(
+            Vec3::new(x, origin.y, 0.0),
+            Vec3::new(x, origin.y + extent.y, 0.0),
+            cell_color,
+        );
+    }
+    for j in 1..ny {
+        let y = origin.y + j as f32 * cell;
+        lines.line(
+            Vec3::new(origin.x, y, 0.0),
+            Vec3::new(origin.x + extent.x, y, 0.0),
+            cell_color,
+        );
+    }
+    let bounds_color = [1.0, 0.85, 0.25, 1.0];
+    let corners = [
+        origin,
+        origin + Vec3::new(extent.x, 0.0, 0.0),
+        origin + extent,
+        origin + Vec3::new(0.0, extent.y, 0.0),
+    ];
+    for i in 0..4 {
+        lines.line(corners[i], corners[(i + 1) % 4], bounds_color);
     }
+    lines.line(Vec3::ZERO, Vec3::X * 0.25, [1.0, 0.2, 0.2, 1.0]);
+    lines.line(Vec3::ZERO, Vec3::Y * 0.25, [0.2, 1.0, 0.2, 1.0]);
+    lines
 }
 
 #[derive(Clone, Debug)]
@@ -569,7 +866,7 @@ struct DemoStats {
     dt_ms: f32,
     update_cpu_ms: f32,
     graph_build_cpu_ms: f32,
-    readback_cpu_ms: f32,
+    readback_latency_ms: f32,
     readback_sync_cpu_ms: f32,
     readback_mismatch: bool,
     cached_order_len: usize,
@@ -596,7 +893,7 @@ impl DemoStats {
             dt_ms: 0.0,
             update_cpu_ms: 0.0,
             graph_build_cpu_ms: 0.0,
-            readback_cpu_ms: 0.0,
+            readback_latency_ms: 0.0,
             readback_sync_cpu_ms: 0.0,
             readback_mismatch: false,
             cached_order_len: 0,
@@ -619,30 +916,22 @@ impl Pass for SpatialGridRebuildPass {
 
     fn execute(&self, ctx: &PassContext) -> wgpu::CommandBuffer {
         let mut encoder = ctx.create_command_encoder(Some(&self.name));
-        self.grid.encode_rebuild(&mut encoder, ctx.resources);
+        self.grid
+            .encode_rebuild(&mut encoder, ctx.resources)
+            .expect("spatial grid resources should be registered");
         encoder.finish()
     }
 }
 
-enum ReadbackState {
-    Idle,
-    Mapping(Arc<Mutex<Option<Result<(), wgpu::BufferAsyncError>>>>),
-}
-
-struct ReadbackSlot {
-    handle: Handle<wgpu::Buffer>,
-    state: ReadbackState,
-}
-
 struct ParticleRendererManager {
     particle_buffer: Handle<wgpu::Buffer>,
     visible_ids: Handle<wgpu::Buffer>,
     dispatch_args: Handle<wgpu::Buffer>,
     draw_args: Handle<wgpu::Buffer>,
     draw_args_sync_readback: Handle<wgpu::Buffer>,
-    draw_args_readback_slot: Handle<FrameBufferHandle>,
-    readback_ring: Vec<ReadbackSlot>,
-    current_readback_index: usize,
+    /// Periodic non-blocking download of `draw_args` for the stats overlay,
+    /// with the time it was issued.
+    draw_args_download: Option<(Download, Instant)>,
     sim_params_buffer: Handle<wgpu::Buffer>,
     reset_bind_group: Handle<wgpu::BindGroup>,
     simulate_bind_group: Handle<wgpu::BindGroup>,
@@ -661,7 +950,7 @@ struct ParticleRendererManager {
     spatial_grid: Arc<SpatialGridGpu>,
     particles_to_grid_pipeline: Handle<wgpu::ComputePipeline>,
     particles_to_grid_bind_group: Handle<wgpu::BindGroup>,
-    particles_to_grid_dispatch_x: u32,
+    particle_count: u32,
     grid_neighbor_stats: Handle<wgpu::Buffer>,
     grid_neighbor_readback: Handle<wgpu::Buffer>,
     clear_grid_neighbor_pipeline: Handle<wgpu::ComputePipeline>,
@@ -675,14 +964,30 @@ struct ParticleRendererManager {
     viewport_w: u32,
     viewport_h: u32,
     sim_view_buffer: Handle<wgpu::Buffer>,
+    /// Edited by the UI; copied to `environment_buffer` when it changes.
+    environment: Arc<Mutex<SceneEnvironment>>,
+    applied_environment: SceneEnvironment,
+    environment_buffer: Handle<wgpu::Buffer>,
+    /// Set when the clear color, debug overlay or scene color target changed, since all
+    /// are baked into the cached frame graph.
+    environment_dirty: bool,
+    debug_lines: DebugLineRenderer,
+    debug_batch: DebugLines,
+    /// Slot for the offscreen view the particles and debug overlay draw into.
+    scene_color: Handle<FrameTextureView>,
+    scene_color_target: SceneColorTarget,
+    surface_format: wgpu::TextureFormat,
+    grade_pipeline: Handle<wgpu::RenderPipeline>,
 }
 
 impl ParticleRendererManager {
+    #[allow(clippy::too_many_arguments)]
     fn new(
         renderer: &Renderer,
         registry: &mut ResourceRegistry,
         surface_format: wgpu::TextureFormat,
         stats: Arc<Mutex<DemoStats>>,
+        environment: Arc<Mutex<SceneEnvironment>>,
         particle_count: usize,
         grid_neighbor_validate: bool,
         viewport_w: u32,
@@ -694,7 +999,7 @@ impl ParticleRendererManager {
             .map(|i| ParticleState::from_index(i, particle_count))
             .collect();
 
-        let dispatch_count = (particle_count as u32).div_ceil(WORKGROUP_SIZE);
+        let dispatch_count = workgroup_count(particle_count as u32, WORKGROUP_SIZE);
 
         let particle_buffer = renderer
             .create_gpu_buffer::<ParticleState>()
@@ -719,30 +1024,12 @@ impl ParticleRendererManager {
             .usage(BufferUsage::Indirect)
             .add_usage(wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC)
             .build(registry)?;
-        let mut readback_ring = Vec::with_capacity(READBACK_RING_SIZE);
-        for index in 0..READBACK_RING_SIZE {
-            let readback = renderer
-                .create_gpu_buffer::<DrawIndirectArgs>()
-                .label(format!("particle draw args readback {index}"))
-                .capacity(1)
-                .usage(BufferUsage::Readback)
-                .build(registry)?;
-            readback_ring.push(ReadbackSlot {
-                handle: readback.handle(),
-                state: ReadbackState::Idle,
-            });
-        }
         let draw_args_sync_readback = renderer
             .create_gpu_buffer::<DrawIndirectArgs>()
             .label("particle draw args sync readback")
             .capacity(1)
             .usage(BufferUsage::Readback)
             .build(registry)?;
-        let draw_args_readback_slot = registry.insert(FrameBufferHandle::new());
-        registry
-            .get(draw_args_readback_slot)
-            .expect("frame buffer readback slot should exist")
-            .set(readback_ring[0].handle);
         let frame_target = registry.insert(FrameTextureView::new());
         let depth_frame = registry.insert(FrameTextureView::new());
         let sim_params_buffer = renderer
@@ -756,10 +1043,24 @@ impl ParticleRendererManager {
             }])
             .usage(BufferUsage::Uniform)
             .build(registry)?;
+        let applied_environment = environment
+            .lock()
+            .map(|environment| *environment)
+            .unwrap_or_default();
         let sim_view_buffer = renderer
             .create_gpu_buffer::<SimViewParams>()
             .label("sim view NDC (aspect)")
-            .with_data(&[sim_view_params_from_viewport(viewport_w, viewport_h)])
+            .with_data(&[sim_view_params_from_viewport(
+                viewport_w,
+                viewport_h,
+                applied_environment.point_scale,
+            )])
+            .usage(BufferUsage::Uniform)
+            .build(registry)?;
+        let environment_buffer = renderer
+            .create_gpu_buffer::<SceneEnvironmentParams>()
+            .label("scene environment")
+            .with_data(&[applied_environment.params()])
             .usage(BufferUsage::Uniform)
             .build(registry)?;
 
@@ -837,7 +1138,6 @@ impl ParticleRendererManager {
             .with_compute_shader(particles_to_grid_shader)
             .with_layout(particles_to_grid_pl)
             .build(registry)?;
-        let particles_to_grid_dispatch_x = (particle_count as u32).div_ceil(WORKGROUP_SIZE);
 
         let grid_neighbor_stats = renderer
             .create_buffer()
@@ -1064,6 +1364,12 @@ impl ParticleRendererManager {
                 sim_view_buffer.handle(),
                 BindingType::Uniform,
             )
+            .buffer_stage(
+                3,
+                ShaderStage::Fragment,
+                environment_buffer.handle(),
+                BindingType::Uniform,
+            )
             .build(registry)?;
 
         let reset_pipeline_layout =
@@ -1154,15 +1460,61 @@ impl ParticleRendererManager {
             })
             .build(registry)?;
 
+        // Drawn without depth so the overlay stays on top of the particles.
+        let debug_lines = DebugLineRenderer::new(
+            renderer,
+            registry,
+            surface_format,
+            None,
+            DepthMode::Standard,
+        )?;
+        let debug_batch = sim_debug_lines(&grid_params);
+
+        let scene_color = registry.insert(FrameTextureView::new());
+        let scene_color_target = SceneColorTarget::new(
+            renderer,
+            registry,
+            scene_color,
+            surface_format,
+            environment_buffer.handle(),
+            viewport_w,
+            viewport_h,
+        )?;
+        let grade_shader = renderer
+            .create_shader_module()
+            .label("color grade")
+            .with_wgsl_source(COLOR_GRADE_SHADER)
+            .build(registry)?;
+        let grade_pipeline_layout =
+            renderer
+                .device()
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("color grade layout"),
+                    bind_group_layouts: &[registry
+                        .get(scene_color_target.layout)
+                        .expect("color grade bind group layout should exist")],
+                    push_constant_ranges: &[],
+                });
+        let grade_pipeline = renderer
+            .create_render_pipeline()
+            .with_label("color grade pipeline")
+            .with_vertex_shader(grade_shader)
+            .with_fragment_shader(grade_shader)
+            .with_layout(grade_pipeline_layout)
+            .with_fragment_target(Some(wgpu::ColorTargetState {
+                format: surface_format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }))
+            .build(registry)?;
+
         Ok(Self {
             particle_buffer: particle_buffer.handle(),
             visible_ids: visible_ids.handle(),
             dispatch_args: dispatch_args.handle(),
             draw_args: draw_args.handle(),
             draw_args_sync_readback: draw_args_sync_readback.handle(),
-            draw_args_readback_slot,
-            readback_ring,
-            current_readback_index: 0,
+            draw_args_download: None,
             sim_params_buffer: sim_params_buffer.handle(),
             reset_bind_group,
             simulate_bind_group,
@@ -1181,7 +1533,7 @@ impl ParticleRendererManager {
             spatial_grid,
             particles_to_grid_pipeline,
             particles_to_grid_bind_group,
-            particles_to_grid_dispatch_x,
+            particle_count: particle_count as u32,
             grid_neighbor_stats,
             grid_neighbor_readback,
             clear_grid_neighbor_pipeline,
@@ -1194,6 +1546,16 @@ impl ParticleRendererManager {
             viewport_w,
             viewport_h,
             sim_view_buffer: sim_view_buffer.handle(),
+            environment,
+            applied_environment,
+            environment_buffer: environment_buffer.handle(),
+            environment_dirty: false,
+            debug_lines,
+            debug_batch,
+            scene_color,
+            scene_color_target,
+            surface_format,
+            grade_pipeline,
         })
     }
 }
@@ -1219,80 +1581,89 @@ impl RendererManager for ParticleRendererManager {
             _reserved1: 0.0,
         }];
         renderer.write_buffer(self.sim_params_buffer, &params, registry)?;
+        let environment = self
+            .environment
+            .lock()
+            .map(|environment| *environment)
+            .unwrap_or(self.applied_environment);
+        if environment != self.applied_environment {
+            renderer.write_buffer(self.environment_buffer, &[environment.params()], registry)?;
+            if environment.clear_color != self.applied_environment.clear_color
+                || environment.debug_geometry != self.applied_environment.debug_geometry
+            {
+                self.environment_dirty = true;
+            }
+            self.applied_environment = environment;
+        }
+        if (
+            self.scene_color_target.width,
+            self.scene_color_target.height,
+        ) != (self.viewport_w, self.viewport_h)
+        {
+            let target = SceneColorTarget::new(
+                renderer,
+                registry,
+                self.scene_color,
+                self.surface_format,
+                self.environment_buffer,
+                self.viewport_w,
+                self.viewport_h,
+            )?;
+            std::mem::replace(&mut self.scene_color_target, target).release(registry);
+            self.environment_dirty = true;
+        }
         let sim_view = [sim_view_params_from_viewport(
             self.viewport_w,
             self.viewport_h,
+            self.applied_environment.point_scale,
         )];
         renderer.write_buffer(self.sim_view_buffer, &sim_view, registry)?;
+        if self.applied_environment.debug_geometry {
+            self.debug_lines.upload(
+                renderer,
+                registry,
+                sim_view_proj(self.viewport_w, self.viewport_h),
+                &self.debug_batch,
+            )?;
+        }
 
         let mut gpu_visible_count = None;
         let mut gpu_visible_count_sync = None;
-        let mut readback_cpu_ms = 0.0;
+        let mut readback_latency_ms = 0.0;
         let mut readback_sync_cpu_ms = 0.0;
         let mut readback_mismatch = None;
-        renderer.device().poll(wgpu::PollType::Poll)?;
-        for slot in &mut self.readback_ring {
-            let completed = match &slot.state {
-                ReadbackState::Idle => None,
-                ReadbackState::Mapping(status) => {
-                    status.lock().ok().and_then(|mut state| state.take())
+        if let Some((download, issued_at)) = &mut self.draw_args_download
+            && let Some(result) = download.try_finish()
+        {
+            match result {
+                Ok(bytes) => {
+                    gpu_visible_count = bytes
+                        .get(..std::mem::size_of::<DrawIndirectArgs>())
+                        .map(bytemuck::pod_read_unaligned::<DrawIndirectArgs>)
+                        .map(|args| args.instance_count);
+                    readback_latency_ms = issued_at.elapsed().as_secs_f32() * 1000.0;
                 }
-            };
-
-            if let Some(result) = completed {
-                match result {
-                    Ok(()) => {
-                        let readback_start = Instant::now();
-                        let buffer = registry
-                            .get(slot.handle)
-                            .expect("readback buffer handle should exist");
-                        {
-                            let mapped = buffer.slice(..).get_mapped_range();
-                            let values = bytemuck::cast_slice::<u8, DrawIndirectArgs>(&mapped);
-                            gpu_visible_count = values.first().map(|args| args.instance_count);
-                        }
-                        buffer.unmap();
-                        readback_cpu_ms = readback_start.elapsed().as_secs_f32() * 1000.0;
-                    }
-                    Err(err) => {
-                        error!(error = %err, "failed to map particle draw args readback buffer");
-                        if let Some(buffer) = registry.get(slot.handle) {
-                            buffer.unmap();
-                        }
-                    }
+                Err(err) => {
+                    error!(error = %err, "failed to read back particle draw args");
                 }
-                slot.state = ReadbackState::Idle;
             }
+            self.draw_args_download = None;
         }
 
         self.frame_index = self.frame_index.wrapping_add(1);
-        if self.frame_index % READBACK_INTERVAL_FRAMES == 0 {
-            if let Some(next_index) = self.next_idle_readback_index() {
-                let current_handle = self.readback_ring[self.current_readback_index].handle;
-                let buffer = registry
-                    .get(current_handle)
-                    .expect("current readback buffer should exist");
-                let slice = buffer.slice(..);
-                let status = Arc::new(Mutex::new(None));
-                let status_for_callback = Arc::clone(&status);
-                slice.map_async(wgpu::MapMode::Read, move |result| {
-                    if let Ok(mut slot) = status_for_callback.lock() {
-                        *slot = Some(result);
-                    }
-                });
-                self.readback_ring[self.current_readback_index].state =
-                    ReadbackState::Mapping(status);
-                self.current_readback_index = next_index;
-                registry
-                    .get(self.draw_args_readback_slot)
-                    .expect("frame buffer readback slot should exist")
-                    .set(self.readback_ring[self.current_readback_index].handle);
-            } else {
-                error!("no idle readback buffer available; skipping GPU stats rotation");
-            }
+        if self.frame_index.is_multiple_of(READBACK_INTERVAL_FRAMES)
+            && self.draw_args_download.is_none()
+        {
+            self.draw_args_download = Some((
+                renderer.read_buffer_async(self.draw_args, registry),
+                Instant::now(),
+            ));
         }
 
-        if self.frame_index % READBACK_VALIDATE_INTERVAL_FRAMES == 0 {
+        if self
+            .frame_index
+            .is_multiple_of(READBACK_VALIDATE_INTERVAL_FRAMES)
+        {
             let readback_start = Instant::now();
             match renderer.read_buffer::<DrawIndirectArgs>(self.draw_args_sync_readback, registry) {
                 Ok(values) => {
@@ -1324,7 +1695,7 @@ impl RendererManager for ParticleRendererManager {
             stats.update_cpu_ms = update_start.elapsed().as_secs_f32() * 1000.0;
             if let Some(count) = gpu_visible_count {
                 stats.gpu_visible_count = count;
-                stats.readback_cpu_ms = readback_cpu_ms;
+                stats.readback_latency_ms = readback_latency_ms;
             }
             if let Some(count) = gpu_visible_count_sync {
                 stats.gpu_visible_count_sync = count;
@@ -1335,13 +1706,13 @@ impl RendererManager for ParticleRendererManager {
             }
         }
 
-        if self.grid_neighbor_validate && self.frame_index % 120 == 0 {
+        if self.grid_neighbor_validate && self.frame_index.is_multiple_of(120) {
             match renderer.read_buffer::<u32>(self.grid_neighbor_readback, registry) {
                 Ok(v) => {
-                    if let Some(m) = v.first() {
-                        if let Ok(mut stats) = self.stats.lock() {
-                            stats.grid_max_others_in_3x3 = *m;
-                        }
+                    if let Some(m) = v.first()
+                        && let Ok(mut stats) = self.stats.lock()
+                    {
+                        stats.grid_max_others_in_3x3 = *m;
                     }
                 }
                 Err(err) => {
@@ -1369,7 +1740,7 @@ impl RendererManager for ParticleRendererManager {
                 .expect("depth frame slot should exist")
                 .set(depth);
         }
-        Ok(false)
+        Ok(std::mem::take(&mut self.environment_dirty))
     }
 
     fn build_frame_graph(&mut self) -> Result<ExecutableFrameGraph, FrameGraphError> {
@@ -1396,7 +1767,7 @@ impl RendererManager for ParticleRendererManager {
             .write(self.spatial_grid.positions)
             .with_pipeline(self.particles_to_grid_pipeline)
             .with_bind_group(0, self.particles_to_grid_bind_group)
-            .dispatch(self.particles_to_grid_dispatch_x, 1, 1)
+            .dispatch_elements(self.particle_count, WORKGROUP_SIZE)
             .build()
             .expect("particles to grid pass should build");
 
@@ -1427,7 +1798,7 @@ impl RendererManager for ParticleRendererManager {
                 .read(self.spatial_grid.sorted_entity_ids)
                 .with_pipeline(self.collision_pipeline)
                 .with_bind_group(0, self.collision_bind_group)
-                .dispatch(self.particles_to_grid_dispatch_x, 1, 1)
+                .dispatch_elements(self.particle_count, WORKGROUP_SIZE)
                 .build()
                 .unwrap_or_else(|_| {
                     panic!("collision pass {iter} should build");
@@ -1450,7 +1821,7 @@ impl RendererManager for ParticleRendererManager {
             .read_write(self.grid_neighbor_stats)
             .with_pipeline(self.grid_neighbor_max_pipeline)
             .with_bind_group(0, self.grid_neighbor_max_bind_group)
-            .dispatch(self.particles_to_grid_dispatch_x, 1, 1)
+            .dispatch_elements(self.particle_count, WORKGROUP_SIZE)
             .build()
             .expect("grid neighbor max pass should build");
 
@@ -1465,17 +1836,11 @@ impl RendererManager for ParticleRendererManager {
             .build()
             .expect("compact pass should build");
 
-        let mut copy_readback = CopyPassBuilder::new("CopyDrawArgsReadback")
-            .copy_buffer_to_frame_slot(
-                self.draw_args,
-                self.draw_args_readback_slot,
-                std::mem::size_of::<DrawIndirectArgs>() as u64,
-            )
-            .copy_buffer(
-                self.draw_args,
-                self.draw_args_sync_readback,
-                std::mem::size_of::<DrawIndirectArgs>() as u64,
-            );
+        let mut copy_readback = CopyPassBuilder::new("CopyDrawArgsReadback").copy_buffer(
+            self.draw_args,
+            self.draw_args_sync_readback,
+            std::mem::size_of::<DrawIndirectArgs>() as u64,
+        );
         if self.grid_neighbor_validate {
             copy_readback =
                 copy_readback.copy_buffer(self.grid_neighbor_stats, self.grid_neighbor_readback, 4);
@@ -1487,16 +1852,12 @@ impl RendererManager for ParticleRendererManager {
         let render_pass = RenderPassBuilder::new("RenderParticles")
             .read(self.particle_buffer)
             .read(self.visible_ids)
+            .read(self.environment_buffer)
             .with_pipeline(self.render_pipeline)
             .with_bind_group(0, self.render_bind_group)
             .with_frame_color_attachment(
-                self.frame_target,
-                triad_gpu::ColorLoadOp::Clear(wgpu::Color {
-                    r: 0.04,
-                    g: 0.05,
-                    b: 0.06,
-                    a: 1.0,
-                }),
+                self.scene_color,
+                triad_gpu::ColorLoadOp::Clear(self.applied_environment.clear_color()),
             )
             .with_frame_depth_stencil_attachment(
                 self.depth_frame,
@@ -1523,6 +1884,25 @@ impl RendererManager for ParticleRendererManager {
         graph.add_pass(compact_pass);
         graph.add_pass(copy_readback_pass);
         graph.add_pass(render_pass);
+        if self.applied_environment.debug_geometry {
+            graph.add_pass(
+                self.debug_lines
+                    .pass("SimDebugLines", self.scene_color, None)?,
+            );
+        }
+        let grade_pass = RenderPassBuilder::new("ColorGrade")
+            .read(self.scene_color)
+            .read(self.environment_buffer)
+            .with_pipeline(self.grade_pipeline)
+            .with_bind_group(0, self.scene_color_target.bind_group)
+            .with_frame_color_attachment(
+                self.frame_target,
+                triad_gpu::ColorLoadOp::Clear(wgpu::Color::BLACK),
+            )
+            .draw(3, 1)
+            .build()
+            .expect("color grade pass should build");
+        graph.add_pass(grade_pass);
 
         let executable = graph.build_with_cached_order(self.cached_execution_order.as_deref())?;
         self.cached_execution_order = Some(executable.execution_order().to_vec());
@@ -1544,13 +1924,20 @@ impl RendererManager for ParticleRendererManager {
         self.viewport_h = height.max(1);
         Ok(())
     }
-}
 
-impl ParticleRendererManager {
-    fn next_idle_readback_index(&self) -> Option<usize> {
-        (1..=self.readback_ring.len())
-            .map(|offset| (self.current_readback_index + offset) % self.readback_ring.len())
-            .find(|&index| matches!(self.readback_ring[index].state, ReadbackState::Idle))
+    fn point_scale(&self) -> f32 {
+        self.environment
+            .lock()
+            .map_or(self.applied_environment.point_scale, |environment| {
+                environment.point_scale
+            })
+    }
+
+    fn set_point_scale(&mut self, scale: f32) {
+        // Picked up by the next `update`, like edits from the Environment panel.
+        if let Ok(mut environment) = self.environment.lock() {
+            environment.set_point_scale(scale);
+        }
     }
 }
 
@@ -1582,21 +1969,88 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     let stats = Arc::new(Mutex::new(DemoStats::new(
         particle_count,
-        (particle_count as u32).div_ceil(WORKGROUP_SIZE),
+        workgroup_count(particle_count as u32, WORKGROUP_SIZE),
         total_cells(SPATIAL_GRID_DIMS),
         grid_neighbor_validate,
     )));
     let ui_stats = Arc::clone(&stats);
     let manager_stats = Arc::clone(&stats);
+    let environment = Arc::new(Mutex::new(SceneEnvironment::default()));
+    let key_bindings = key_bindings_from_env();
+    let ui_environment = Arc::clone(&environment);
 
     let result = run_with_renderer_config(
         "Triad",
-        WindowConfig {
-            present_mode: wgpu::PresentMode::Fifo,
-        },
+        WindowConfig::default()
+            .with_renderer_config(
+                RendererConfig::default().with_present_mode(wgpu::PresentMode::Fifo),
+            )
+            .with_key_bindings(key_bindings.clone()),
         |controls| {
+            let key_environment = Arc::clone(&ui_environment);
+            controls.on_frame(move |frame| {
+                let step = if key_bindings.just_pressed(ACTION_POINT_SCALE_UP, frame.input) {
+                    POINT_SCALE_STEP
+                } else if key_bindings.just_pressed(ACTION_POINT_SCALE_DOWN, frame.input) {
+                    POINT_SCALE_STEP.recip()
+                } else {
+                    return;
+                };
+                if let Ok(mut environment) = key_environment.lock() {
+                    let scale = environment.point_scale * step;
+                    environment.set_point_scale(scale);
+                }
+            });
             let ui_stats = Arc::clone(&ui_stats);
+            let ui_environment = Arc::clone(&ui_environment);
             controls.on_ui(move |ctx| {
+                if let Ok(mut environment) = ui_environment.lock() {
+                    egui::Window::new("Environment")
+                        .default_pos(egui::pos2(16.0, 560.0))
+                        .default_open(false)
+                        .resizable(false)
+                        .show(ctx, |ui| {
+                            ui.horizontal(|ui| {
+                                ui.label("Clear color");
+                                ui.color_edit_button_rgb(&mut environment.clear_color);
+                            });
+                            ui.horizontal(|ui| {
+                                ui.label("Ambient tint");
+                                ui.color_edit_button_rgb(&mut environment.ambient_tint);
+                            });
+                            ui.add(
+                                egui::Slider::new(
+                                    &mut environment.point_scale,
+                                    MIN_POINT_SCALE..=MAX_POINT_SCALE,
+                                )
+                                .logarithmic(true)
+                                .text("Point scale"),
+                            );
+                            ui.checkbox(&mut environment.debug_geometry, "Debug geometry");
+                            ui.separator();
+                            ui.label("Color grading");
+                            let grading = &mut environment.grading;
+                            ui.add(
+                                egui::Slider::new(&mut grading.brightness, -0.5..=0.5)
+                                    .text("Brightness"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut grading.contrast, 0.0..=2.0)
+                                    .text("Contrast"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut grading.saturation, 0.0..=2.0)
+                                    .text("Saturation"),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut grading.temperature, -1.0..=1.0)
+                                    .text("Temperature"),
+                            );
+                            if ui.button("Reset").clicked() {
+                                *environment = SceneEnvironment::default();
+                            }
+                        });
+                }
                 let Ok(stats) = ui_stats.lock() else {
                     return;
                 };
@@ -1645,7 +2099,10 @@ fn main() -> Result<(), Box<dyn Error>> {
                             "Graph build CPU: {:.3} ms",
                             stats.graph_build_cpu_ms
                         ));
-                        ui.label(format!("Readback CPU: {:.3} ms", stats.readback_cpu_ms));
+                        ui.label(format!(
+                            "Readback latency: {:.3} ms",
+                            stats.readback_latency_ms
+                        ));
                         ui.label(format!(
                             "Readback sync CPU: {:.3} ms",
                             stats.readback_sync_cpu_ms
@@ -1672,6 +2129,7 @@ fn main() -> Result<(), Box<dyn Error>> {
                 registry,
                 surface_format,
                 Arc::clone(&manager_stats),
+                Arc::clone(&environment),
                 particle_count,
                 grid_neighbor_validate,
                 width,
@@ -0,0 +1,493 @@
+//! Scene backdrop: a gradient sky and an infinite, distance-faded ground grid.
+//!
+//! Both are drawn as a single fullscreen triangle that reconstructs view rays
+//! from the inverse view-projection matrix. Add [`BackgroundRenderer::sky_pass`]
+//! before the scene passes (it clears the color target) and, when a grid is
+//! configured, [`BackgroundRenderer::grid_pass`] after them. The grid writes
+//! `frag_depth` for the ground plane and is depth-tested against the scene
+//! without writing depth, so geometry above the plane occludes it.
+
+use crate::builder::{BindingType, BufferUsage, ShaderStage};
+use crate::error::{BindGroupError, GpuError, RenderPassError};
+use crate::frame_graph::{Handle, PassBuilder};
+use crate::render::{ColorLoadOp, DepthLoadOp, DepthMode, RenderPassBuilder};
+use crate::resource_registry::ResourceRegistry;
+use crate::{FrameTextureView, Renderer};
+use glam::{Mat4, Vec3};
+
+const BACKGROUND_COMMON: &str = r#"
+struct BackgroundUniforms {
+    inv_view_proj: mat4x4<f32>,
+    view_proj: mat4x4<f32>,
+    camera_position: vec4<f32>,
+    sky_zenith: vec4<f32>,
+    sky_horizon: vec4<f32>,
+    sky_ground: vec4<f32>,
+    grid_minor: vec4<f32>,
+    grid_major: vec4<f32>,
+    // x = plane height, y = cell size, z = cells per major line, w = fade distance
+    grid_params: vec4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> u: BackgroundUniforms;
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) ndc: vec2<f32>,
+};
+
+@vertex
+fn vs_main(@builtin(vertex_index) index: u32) -> VsOut {
+    let uv = vec2<f32>(f32((index << 1u) & 2u), f32(index & 2u));
+    let ndc = uv * 2.0 - 1.0;
+    var out: VsOut;
+    out.clip_pos = vec4<f32>(ndc, 0.0, 1.0);
+    out.ndc = ndc;
+    return out;
+}
+
+fn world_at(ndc: vec2<f32>, depth: f32) -> vec3<f32> {
+    let p = u.inv_view_proj * vec4<f32>(ndc, depth, 1.0);
+    return p.xyz / p.w;
+}
+"#;
+
+const SKY_FRAGMENT: &str = r#"
+@fragment
+fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
+    let dir = normalize(world_at(in.ndc, 0.5) - u.camera_position.xyz);
+    let up = sqrt(clamp(dir.y, 0.0, 1.0));
+    let down = sqrt(clamp(-dir.y, 0.0, 1.0));
+    let sky = mix(u.sky_horizon.rgb, u.sky_zenith.rgb, up);
+    let color = mix(sky, u.sky_ground.rgb, down);
+    return vec4<f32>(color, 1.0);
+}
+"#;
+
+const GRID_FRAGMENT: &str = r#"
+struct GridOut {
+    @location(0) color: vec4<f32>,
+    @builtin(frag_depth) depth: f32,
+};
+
+// Coverage of the nearest grid line, anti-aliased over one screen pixel.
+fn grid_coverage(coord: vec2<f32>) -> f32 {
+    let width = max(fwidth(coord), vec2<f32>(1e-6));
+    let dist = abs(fract(coord - 0.5) - 0.5) / width;
+    return 1.0 - min(min(dist.x, dist.y), 1.0);
+}
+
+@fragment
+fn fs_main(in: VsOut) -> GridOut {
+    // Rays start at the eye rather than the near plane so both depth modes and
+    // infinite far planes unproject to finite points.
+    let near = u.camera_position.xyz;
+    let ray = world_at(in.ndc, 0.5) - near;
+    let facing = abs(ray.y) > 1e-6;
+    let t = (u.grid_params.x - near.y) / select(1e-6, ray.y, facing);
+    let hit = near + ray * t;
+
+    // Derivatives must be taken in uniform control flow, so nothing is
+    // discarded until coverage has been computed.
+    let cell = max(u.grid_params.y, 1e-4);
+    let minor = grid_coverage(hit.xz / cell) * u.grid_minor.a;
+    let major = grid_coverage(hit.xz / (cell * max(u.grid_params.z, 1.0))) * u.grid_major.a;
+
+    let distance = length(hit.xz - u.camera_position.xz);
+    let fade = 1.0 - smoothstep(u.grid_params.w * 0.25, u.grid_params.w, distance);
+    let alpha = max(minor, major) * fade;
+
+    let clip = u.view_proj * vec4<f32>(hit, 1.0);
+    let depth = clip.z / clip.w;
+    if !facing || t <= 0.0 || alpha <= 0.0 || depth < 0.0 || depth > 1.0 {
+        discard;
+    }
+
+    var out: GridOut;
+    let rgb = select(u.grid_minor.rgb, u.grid_major.rgb, major >= minor);
+    out.color = vec4<f32>(rgb, alpha);
+    out.depth = depth;
+    return out;
+}
+"#;
+
+/// Vertical gradient used in place of a flat clear color.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SkyGradient {
+    /// Color straight up
+    pub zenith: [f32; 3],
+    /// Color at the horizon, shared by the upper and lower halves
+    pub horizon: [f32; 3],
+    /// Color straight down
+    pub ground: [f32; 3],
+}
+
+impl Default for SkyGradient {
+    fn default() -> Self {
+        Self {
+            zenith: [0.05, 0.07, 0.12],
+            horizon: [0.16, 0.18, 0.22],
+            ground: [0.06, 0.06, 0.07],
+        }
+    }
+}
+
+/// Infinite ground grid on the plane `y = height`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GroundGrid {
+    pub height: f32,
+    /// World-space size of one minor cell
+    pub cell_size: f32,
+    /// Minor cells between major lines
+    pub major_every: u32,
+    /// Distance from the camera at which the grid has fully faded out
+    pub fade_distance: f32,
+    /// RGBA of minor lines; alpha scales line opacity
+    pub minor_color: [f32; 4],
+    /// RGBA of major lines; alpha scales line opacity
+    pub major_color: [f32; 4],
+}
+
+impl Default for GroundGrid {
+    fn default() -> Self {
+        Self {
+            height: 0.0,
+            cell_size: 1.0,
+            major_every: 10,
+            fade_distance: 100.0,
+            minor_color: [0.5, 0.5, 0.55, 0.35],
+            major_color: [0.7, 0.7, 0.75, 0.7],
+        }
+    }
+}
+
+/// Which backdrop elements to draw.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BackgroundConfig {
+    /// Gradient sky; `None` clears to `clear_color`
+    pub sky: Option<SkyGradient>,
+    /// Ground grid; `None` disables [`BackgroundRenderer::grid_pass`]
+    pub grid: Option<GroundGrid>,
+    pub clear_color: wgpu::Color,
+}
+
+impl Default for BackgroundConfig {
+    fn default() -> Self {
+        Self {
+            sky: Some(SkyGradient::default()),
+            grid: Some(GroundGrid::default()),
+            clear_color: wgpu::Color::BLACK,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+struct BackgroundUniforms {
+    inv_view_proj: [[f32; 4]; 4],
+    view_proj: [[f32; 4]; 4],
+    camera_position: [f32; 4],
+    sky_zenith: [f32; 4],
+    sky_horizon: [f32; 4],
+    sky_ground: [f32; 4],
+    grid_minor: [f32; 4],
+    grid_major: [f32; 4],
+    grid_params: [f32; 4],
+}
+
+impl BackgroundUniforms {
+    fn new(config: &BackgroundConfig, view_proj: Mat4, camera_position: Vec3) -> Self {
+        let sky = config.sky.unwrap_or_default();
+        let grid = config.grid.unwrap_or_default();
+        let rgb = |c: [f32; 3]| [c[0], c[1], c[2], 1.0];
+        Self {
+            inv_view_proj: view_proj.inverse().to_cols_array_2d(),
+            view_proj: view_proj.to_cols_array_2d(),
+            camera_position: camera_position.extend(1.0).to_array(),
+            sky_zenith: rgb(sky.zenith),
+            sky_horizon: rgb(sky.horizon),
+            sky_ground: rgb(sky.ground),
+            grid_minor: grid.minor_color,
+            grid_major: grid.major_color,
+            grid_params: [
+                grid.height,
+                grid.cell_size,
+                grid.major_every as f32,
+                grid.fade_distance,
+            ],
+        }
+    }
+}
+
+/// GPU resources for the sky and ground grid passes.
+pub struct BackgroundRenderer {
+    config: BackgroundConfig,
+    sky_pipeline: Handle<wgpu::RenderPipeline>,
+    grid_pipeline: Handle<wgpu::RenderPipeline>,
+    bind_group: Handle<wgpu::BindGroup>,
+    uniforms: Handle<wgpu::Buffer>,
+}
+
+impl BackgroundRenderer {
+    /// Create both pipelines for `color_format`. The grid is depth-tested
+    /// against `depth_format` in `depth_mode`, which must match the scene depth target.
+    pub fn new(
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        color_format: wgpu::TextureFormat,
+        depth_format: wgpu::TextureFormat,
+        depth_mode: DepthMode,
+        config: BackgroundConfig,
+    ) -> Result<Self, GpuError> {
+        let uniforms = renderer
+            .create_gpu_buffer::<BackgroundUniforms>()
+            .label("background uniforms")
+            .with_data(&[BackgroundUniforms::new(&config, Mat4::IDENTITY, Vec3::ZERO)])
+            .usage(BufferUsage::Uniform)
+            .build(registry)?;
+
+        let sky_shader = renderer
+            .create_shader_module()
+            .label("background sky")
+            .with_wgsl_source(format!("{BACKGROUND_COMMON}{SKY_FRAGMENT}"))
+            .build(registry)?;
+        let grid_shader = renderer
+            .create_shader_module()
+            .label("background grid")
+            .with_wgsl_source(format!("{BACKGROUND_COMMON}{GRID_FRAGMENT}"))
+            .build(registry)?;
+
+        let (layout, bind_group) = renderer
+            .create_bind_group()
+            .label("background")
+            .buffer_stage(
+                0,
+                ShaderStage::VertexFragment,
+                uniforms.handle(),
+                BindingType::Uniform,
+            )
+            .build(registry)?;
+        let layout = registry.get(layout).ok_or(BindGroupError::LayoutNotFound)?;
+        let pipeline_layout = || {
+            renderer
+                .device()
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("background layout"),
+                    bind_group_layouts: &[layout],
+                    push_constant_ranges: &[],
+                })
+        };
+        let sky_layout = pipeline_layout();
+        let grid_layout = pipeline_layout();
+
+        let sky_pipeline = renderer
+            .create_render_pipeline()
+            .with_label("background sky")
+            .with_vertex_shader(sky_shader)
+            .with_fragment_shader(sky_shader)
+            .with_layout(sky_layout)
+            .with_fragment_target(Some(wgpu::ColorTargetState {
+                format: color_format,
+                blend: None,
+                write_mask: wgpu::ColorWrites::ALL,
+            }))
+            .build(registry)?;
+
+        let grid_pipeline = renderer
+            .create_render_pipeline()
+            .with_label("background grid")
+            .with_vertex_shader(grid_shader)
+            .with_fragment_shader(grid_shader)
+            .with_layout(grid_layout)
+            .with_fragment_target(Some(wgpu::ColorTargetState {
+                format: color_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            }))
+            .with_depth_mode(depth_mode)
+            .with_depth_stencil(wgpu::DepthStencilState {
+                format: depth_format,
+                depth_write_enabled: false,
+                depth_compare: wgpu::CompareFunction::LessEqual,
+                stencil: wgpu::StencilState::default(),
+                bias: wgpu::DepthBiasState::default(),
+            })
+            .build(registry)?;
+
+        Ok(Self {
+            config,
+            sky_pipeline,
+            grid_pipeline,
+            bind_group,
+            uniforms: uniforms.handle(),
+        })
+    }
+
+    pub fn config(&self) -> &BackgroundConfig {
+        &self.config
+    }
+
+    /// Replace the configuration. Takes effect on the next [`upload`](Self::upload).
+    pub fn set_config(&mut self, config: BackgroundConfig) {
+        self.config = config;
+    }
+
+    /// Upload this frame's camera and colors.
+    pub fn upload(
+        &self,
+        renderer: &Renderer,
+        registry: &ResourceRegistry,
+        view_proj: Mat4,
+        camera_position: Vec3,
+    ) -> Result<(), GpuError> {
+        let uniforms = BackgroundUniforms::new(&self.config, view_proj, camera_position);
+        renderer.write_buffer(self.uniforms, &[uniforms], registry)?;
+        Ok(())
+    }
+
+    /// Pass that clears `color` and fills it with the sky gradient.
+    /// With no sky configured it only clears to `clear_color`.
+    pub fn sky_pass(
+        &self,
+        name: impl Into<String>,
+        color: Handle<FrameTextureView>,
+    ) -> Result<PassBuilder, RenderPassError> {
+        let vertex_count = if self.config.sky.is_some() { 3 } else { 0 };
+        RenderPassBuilder::new(name)
+            .read(self.uniforms)
+            .with_pipeline(self.sky_pipeline)
+            .with_bind_group(0, self.bind_group)
+            .with_frame_color_attachment(color, ColorLoadOp::Clear(self.config.clear_color))
+            .draw(vertex_count, 1)
+            .build()
+    }
+
+    /// Pass that blends the ground grid over `color`, tested against `depth`.
+    /// Returns `None` when no grid is configured.
+    pub fn grid_pass(
+        &self,
+        name: impl Into<String>,
+        color: Handle<FrameTextureView>,
+        depth: Handle<FrameTextureView>,
+    ) -> Option<Result<PassBuilder, RenderPassError>> {
+        self.config.grid?;
+        Some(
+            RenderPassBuilder::new(name)
+                .read(self.uniforms)
+                .with_pipeline(self.grid_pipeline)
+                .with_bind_group(0, self.bind_group)
+                .with_frame_color_attachment(color, ColorLoadOp::Load)
+                .with_frame_depth_stencil_attachment(
+                    depth,
+                    DepthLoadOp::Load,
+                    wgpu::StoreOp::Store,
+                    None,
+                )
+                .draw(3, 1)
+                .build(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FrameGraph;
+    use pollster::FutureExt;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_background_uniforms_pack_config() {
+        let config = BackgroundConfig {
+            grid: Some(GroundGrid {
+                height: 0.5,
+                cell_size: 2.0,
+                major_every: 5,
+                fade_distance: 40.0,
+                ..GroundGrid::default()
+            }),
+            ..BackgroundConfig::default()
+        };
+        let uniforms = BackgroundUniforms::new(&config, Mat4::IDENTITY, Vec3::new(1.0, 2.0, 3.0));
+        assert_eq!(uniforms.grid_params, [0.5, 2.0, 5.0, 40.0]);
+        assert_eq!(uniforms.camera_position, [1.0, 2.0, 3.0, 1.0]);
+        assert_eq!(uniforms.sky_zenith[3], 1.0);
+        assert_eq!(std::mem::size_of::<BackgroundUniforms>() % 16, 0);
+    }
+
+    #[test]
+    fn test_background_passes_execute() {
+        let renderer = match Renderer::new().block_on() {
+            Ok(renderer) => renderer,
+            Err(err) => {
+                eprintln!("skipping background test: {err}");
+                return;
+            }
+        };
+        let mut registry = ResourceRegistry::default();
+        let color_format = wgpu::TextureFormat::Rgba8Unorm;
+        let depth_format = wgpu::TextureFormat::Depth32Float;
+        let mut background = BackgroundRenderer::new(
+            &renderer,
+            &mut registry,
+            color_format,
+            depth_format,
+            DepthMode::Standard,
+            BackgroundConfig::default(),
+        )
+        .expect("background renderer");
+
+        let view = Mat4::look_at_rh(Vec3::new(0.0, 3.0, 8.0), Vec3::ZERO, Vec3::Y);
+        let proj = Mat4::perspective_rh(1.0, 1.0, 0.1, 100.0);
+        background
+            .upload(&renderer, &registry, proj * view, Vec3::new(0.0, 3.0, 8.0))
+            .expect("upload");
+
+        let target = |format, label| {
+            renderer
+                .device()
+                .create_texture(&wgpu::TextureDescriptor {
+                    label: Some(label),
+                    size: wgpu::Extent3d {
+                        width: 16,
+                        height: 16,
+                        depth_or_array_layers: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: wgpu::TextureDimension::D2,
+                    format,
+                    usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                    view_formats: &[],
+                })
+                .create_view(&Default::default())
+        };
+        let color = registry.insert(FrameTextureView::new());
+        let depth = registry.insert(FrameTextureView::new());
+        registry
+            .get(color)
+            .expect("color slot")
+            .set(Arc::new(target(color_format, "background color")));
+        registry
+            .get(depth)
+            .expect("depth slot")
+            .set(Arc::new(target(depth_format, "background depth")));
+
+        let mut graph = FrameGraph::new();
+        graph.add_pass(background.sky_pass("Sky", color).expect("sky pass"));
+        graph.add_pass(
+            background
+                .grid_pass("Grid", color, depth)
+                .expect("grid configured")
+                .expect("grid pass"),
+        );
+        let mut executable = graph.build().expect("frame graph");
+        executable.execute(renderer.device(), renderer.queue(), &registry);
+
+        background.set_config(BackgroundConfig {
+            grid: None,
+            ..BackgroundConfig::default()
+        });
+        assert!(background.grid_pass("Grid", color, depth).is_none());
+    }
+}
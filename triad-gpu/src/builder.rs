@@ -38,7 +38,7 @@ pub enum BufferUsage {
 }
 
 impl BufferUsage {
-    fn to_wgpu(&self) -> wgpu::BufferUsages {
+    fn to_wgpu(self) -> wgpu::BufferUsages {
         match self {
             BufferUsage::Vertex => wgpu::BufferUsages::VERTEX,
             BufferUsage::Index => wgpu::BufferUsages::INDEX,
@@ -556,12 +556,25 @@ impl<'a, T: bytemuck::Pod> GpuBufferBuilder<'a, T> {
 ///
 /// The underlying buffer handle remains constant, so bind groups don't need
 /// to be recreated when data is updated.
+///
+/// For streaming data, [`append`](Self::append) grows the buffer by doubling
+/// its capacity, and [`push_ring`](Self::push_ring) overwrites the oldest
+/// elements in place. Growth keeps the handle but swaps the `wgpu::Buffer`
+/// behind it, so bind groups must be rebuilt when [`generation`](Self::generation) changes.
+///
+/// The two modes can be mixed: any write that extends [`len`](Self::len), and any
+/// growth, moves the ring head to the end of the live elements, so the next
+/// `push_ring` fills free space before overwriting anything.
 #[derive(Debug)]
 pub struct DynamicBuffer<T: bytemuck::Pod> {
     buffer: Handle<wgpu::Buffer>,
     capacity: usize,
     len: usize,
     element_size: usize,
+    label: Option<String>,
+    usage: wgpu::BufferUsages,
+    generation: u64,
+    ring_head: usize,
     _marker: PhantomData<T>,
 }
 
@@ -586,6 +599,11 @@ impl<T: bytemuck::Pod> DynamicBuffer<T> {
         self.len == 0
     }
 
+    /// Number of times the underlying buffer has been reallocated
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
     /// Update element at index
     pub fn update_at(
         &self,
@@ -621,9 +639,10 @@ impl<T: bytemuck::Pod> DynamicBuffer<T> {
         }
         let offset = (start_index * self.element_size) as u64;
         renderer.write_buffer_offset(self.buffer, offset, elements, registry)?;
-        // Extend len if we wrote past current end
+        // Extend len if we wrote past current end; the ring continues after it.
         if end_index > self.len {
             self.len = end_index;
+            self.ring_head = self.len % self.capacity;
         }
         Ok(())
     }
@@ -640,9 +659,120 @@ impl<T: bytemuck::Pod> DynamicBuffer<T> {
         Ok(start_index)
     }
 
+    /// Append elements, doubling capacity as needed. Returns the start index.
+    pub fn append(
+        &mut self,
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        elements: &[T],
+    ) -> Result<usize, BufferError> {
+        self.reserve(renderer, registry, elements.len())?;
+        self.push(renderer, registry, elements)
+    }
+
+    /// Ensure room for `additional` more elements, doubling capacity until they fit.
+    ///
+    /// Returns `true` if the buffer was reallocated. The handle stays the same:
+    /// the registry entry is replaced and existing elements are copied on the GPU,
+    /// oldest first if the ring had wrapped.
+    pub fn reserve(
+        &mut self,
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        additional: usize,
+    ) -> Result<bool, BufferError> {
+        let required = self.len + additional;
+        if required <= self.capacity {
+            return Ok(false);
+        }
+        let mut new_capacity = self.capacity.max(1);
+        while new_capacity < required {
+            new_capacity *= 2;
+        }
+
+        let old_buffer = registry.get(self.buffer).ok_or(BufferError::NotFound)?;
+        let new_buffer = renderer.device().create_buffer(&wgpu::BufferDescriptor {
+            label: self.label.as_deref(),
+            size: aligned_buffer_size(new_capacity * self.element_size),
+            usage: self.usage,
+            mapped_at_creation: false,
+        });
+
+        // A wrapped ring is unrolled so the oldest element lands at index 0 and
+        // later ring writes continue to overwrite in age order.
+        let wrapped = self.len == self.capacity && self.ring_head != 0;
+        let head_bytes = (self.ring_head * self.element_size) as u64;
+        let len_bytes = (self.len * self.element_size) as u64;
+        if wrapped
+            && (!head_bytes.is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT)
+                || !len_bytes.is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT))
+        {
+            return Err(BufferError::UnalignedRingGrow { offset: head_bytes });
+        }
+
+        let copy_size = aligned_buffer_size(self.len * self.element_size).min(old_buffer.size());
+        if copy_size > 0 {
+            let mut encoder =
+                renderer
+                    .device()
+                    .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                        label: Some("DynamicBuffer Grow"),
+                    });
+            if wrapped {
+                let tail_bytes = len_bytes - head_bytes;
+                encoder.copy_buffer_to_buffer(old_buffer, head_bytes, &new_buffer, 0, tail_bytes);
+                encoder.copy_buffer_to_buffer(old_buffer, 0, &new_buffer, tail_bytes, head_bytes);
+            } else {
+                encoder.copy_buffer_to_buffer(old_buffer, 0, &new_buffer, 0, copy_size);
+            }
+            renderer.queue().submit(Some(encoder.finish()));
+        }
+
+        *registry.get_mut(self.buffer).ok_or(BufferError::NotFound)? = new_buffer;
+        self.capacity = new_capacity;
+        self.ring_head = self.len;
+        self.generation += 1;
+        Ok(true)
+    }
+
+    /// Write elements into the buffer as a ring, overwriting the oldest elements
+    /// once capacity is reached. Returns the index of the first element written.
+    ///
+    /// If more than `capacity` elements are given, only the newest `capacity` are kept.
+    pub fn push_ring(
+        &mut self,
+        renderer: &Renderer,
+        registry: &ResourceRegistry,
+        elements: &[T],
+    ) -> Result<usize, BufferError> {
+        if elements.is_empty() {
+            return Ok(self.ring_head);
+        }
+        if self.capacity == 0 {
+            return Err(BufferError::CapacityExceeded {
+                requested: elements.len(),
+                capacity: 0,
+            });
+        }
+        let elements = &elements[elements.len().saturating_sub(self.capacity)..];
+        let start = self.ring_head;
+        let first = (self.capacity - start).min(elements.len());
+
+        let offset = (start * self.element_size) as u64;
+        renderer.write_buffer_offset(self.buffer, offset, &elements[..first], registry)?;
+        if first < elements.len() {
+            renderer.write_buffer_offset(self.buffer, 0, &elements[first..], registry)?;
+        }
+
+        self.ring_head = (start + elements.len()) % self.capacity;
+        self.len = (self.len + elements.len()).min(self.capacity);
+        Ok(start)
+    }
+
     /// Clear (logical only, doesn't zero memory)
     pub fn clear(&mut self) {
         self.len = 0;
+        self.ring_head = 0;
     }
 
     /// Set length (for cases where GPU compute modified data)
@@ -654,6 +784,7 @@ impl<T: bytemuck::Pod> DynamicBuffer<T> {
             });
         }
         self.len = len;
+        self.ring_head = len % self.capacity.max(1);
         Ok(())
     }
 }
@@ -698,7 +829,7 @@ impl<'a, T: bytemuck::Pod> DynamicBufferBuilder<'a, T> {
         self
     }
 
-    /// Add extra usage flags on top of STORAGE | COPY_DST.
+    /// Add extra usage flags on top of STORAGE | COPY_DST | COPY_SRC.
     pub fn add_usage(mut self, usage: wgpu::BufferUsages) -> Self {
         self.additional_usage |= usage;
         self
@@ -717,7 +848,12 @@ impl<'a, T: bytemuck::Pod> DynamicBufferBuilder<'a, T> {
             (None, None) => return Err(BufferError::MissingSizeOrData),
         };
 
-        let buffer_size = (capacity * element_size) as u64;
+        let buffer_size = aligned_buffer_size(capacity * element_size);
+        // COPY_SRC lets `reserve` carry existing contents into a larger buffer.
+        let usage = wgpu::BufferUsages::STORAGE
+            | wgpu::BufferUsages::COPY_DST
+            | wgpu::BufferUsages::COPY_SRC
+            | self.additional_usage;
 
         let buffer = if let Some(data) = self.initial_data {
             // Create with initial data, but allocate full capacity
@@ -728,17 +864,13 @@ impl<'a, T: bytemuck::Pod> DynamicBufferBuilder<'a, T> {
                 .create_buffer_init(&wgpu::util::BufferInitDescriptor {
                     label: self.label.as_deref(),
                     contents: &padded,
-                    usage: wgpu::BufferUsages::STORAGE
-                        | wgpu::BufferUsages::COPY_DST
-                        | self.additional_usage,
+                    usage,
                 })
         } else {
             self.device.create_buffer(&wgpu::BufferDescriptor {
                 label: self.label.as_deref(),
                 size: buffer_size,
-                usage: wgpu::BufferUsages::STORAGE
-                    | wgpu::BufferUsages::COPY_DST
-                    | self.additional_usage,
+                usage,
                 mapped_at_creation: false,
             })
         };
@@ -750,11 +882,20 @@ impl<'a, T: bytemuck::Pod> DynamicBufferBuilder<'a, T> {
             capacity,
             len: initial_len,
             element_size,
+            label: self.label,
+            usage,
+            generation: 0,
+            ring_head: initial_len % capacity.max(1),
             _marker: PhantomData,
         })
     }
 }
 
+/// Round a byte size up to `wgpu::COPY_BUFFER_ALIGNMENT` so the buffer can be copied whole.
+fn aligned_buffer_size(bytes: usize) -> u64 {
+    wgpu::util::align_to(bytes as u64, wgpu::COPY_BUFFER_ALIGNMENT)
+}
+
 /// Builder for creating compute pipelines.
 pub struct ComputePipelineBuilder<'a> {
     device: &'a wgpu::Device,
@@ -856,7 +997,7 @@ pub enum BindingType {
 }
 
 impl BindingType {
-    fn to_wgpu_binding_type(&self) -> wgpu::BindingType {
+    fn to_wgpu_binding_type(self) -> wgpu::BindingType {
         match self {
             BindingType::Uniform => wgpu::BindingType::Buffer {
                 ty: wgpu::BufferBindingType::Uniform,
@@ -878,20 +1019,20 @@ impl BindingType {
                 view_dimension,
                 multisampled,
             } => wgpu::BindingType::Texture {
-                sample_type: *sample_type,
-                view_dimension: *view_dimension,
-                multisampled: *multisampled,
+                sample_type,
+                view_dimension,
+                multisampled,
             },
             BindingType::StorageTexture {
                 access,
                 format,
                 view_dimension,
             } => wgpu::BindingType::StorageTexture {
-                access: *access,
-                format: *format,
-                view_dimension: *view_dimension,
+                access,
+                format,
+                view_dimension,
             },
-            BindingType::Sampler { filtering } => wgpu::BindingType::Sampler(if *filtering {
+            BindingType::Sampler { filtering } => wgpu::BindingType::Sampler(if filtering {
                 wgpu::SamplerBindingType::Filtering
             } else {
                 wgpu::SamplerBindingType::NonFiltering
@@ -914,7 +1055,7 @@ pub enum ShaderStage {
 }
 
 impl ShaderStage {
-    fn to_wgpu(&self) -> wgpu::ShaderStages {
+    fn to_wgpu(self) -> wgpu::ShaderStages {
         match self {
             ShaderStage::Vertex => wgpu::ShaderStages::VERTEX,
             ShaderStage::Fragment => wgpu::ShaderStages::FRAGMENT,
@@ -2,7 +2,16 @@ use crate::error::ComputePassError;
 use crate::frame_graph::pass::{Pass, PassBuilder, PassContext};
 use crate::frame_graph::{Handle, ResourceType};
 
-#[derive(Debug, Clone, Copy)]
+/// Workgroups needed to cover `count` invocations, `count.div_ceil(workgroup_size)`.
+///
+/// # Panics
+/// If `workgroup_size` is zero.
+#[inline]
+pub const fn workgroup_count(count: u32, workgroup_size: u32) -> u32 {
+    count.div_ceil(workgroup_size)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ComputeDispatch {
     Direct {
         x: u32,
@@ -23,6 +32,22 @@ impl ComputeDispatch {
     pub fn indirect(buffer: Handle<wgpu::Buffer>, offset: u64) -> Self {
         Self::Indirect { buffer, offset }
     }
+
+    /// One invocation per element along x, for a kernel declared with
+    /// `@workgroup_size(workgroup_size)`.
+    pub fn for_elements(count: u32, workgroup_size: u32) -> Self {
+        Self::direct(workgroup_count(count, workgroup_size), 1, 1)
+    }
+
+    /// One invocation per cell of a 2D/3D `extent`, for a kernel declared with
+    /// `@workgroup_size(x, y, z)`.
+    pub fn for_extent(extent: [u32; 3], workgroup_size: [u32; 3]) -> Self {
+        Self::direct(
+            workgroup_count(extent[0], workgroup_size[0]),
+            workgroup_count(extent[1], workgroup_size[1]),
+            workgroup_count(extent[2], workgroup_size[2]),
+        )
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -137,6 +162,18 @@ impl ComputePassBuilder {
         self
     }
 
+    /// Dispatch enough workgroups for `count` elements; see [`ComputeDispatch::for_elements`].
+    pub fn dispatch_elements(mut self, count: u32, workgroup_size: u32) -> Self {
+        self.dispatch = Some(ComputeDispatch::for_elements(count, workgroup_size));
+        self
+    }
+
+    /// Dispatch enough workgroups for `extent`; see [`ComputeDispatch::for_extent`].
+    pub fn dispatch_extent(mut self, extent: [u32; 3], workgroup_size: [u32; 3]) -> Self {
+        self.dispatch = Some(ComputeDispatch::for_extent(extent, workgroup_size));
+        self
+    }
+
     pub fn dispatch_indirect(mut self, buffer: Handle<wgpu::Buffer>, offset: u64) -> Self {
         self.dispatch = Some(ComputeDispatch::indirect(buffer, offset));
         self
@@ -197,6 +234,21 @@ mod tests {
         assert!(matches!(err, ComputePassError::MissingDispatch));
     }
 
+    #[test]
+    fn test_dispatch_sizing_rounds_up() {
+        assert_eq!(workgroup_count(0, 64), 0);
+        assert_eq!(workgroup_count(64, 64), 1);
+        assert_eq!(workgroup_count(65, 64), 2);
+        assert_eq!(
+            ComputeDispatch::for_elements(1000, 256),
+            ComputeDispatch::direct(4, 1, 1)
+        );
+        assert_eq!(
+            ComputeDispatch::for_extent([1920, 1080, 1], [16, 16, 1]),
+            ComputeDispatch::direct(120, 68, 1)
+        );
+    }
+
     #[test]
     fn test_compute_pass_executes_direct_dispatch() {
         let renderer = Renderer::new().block_on().expect("renderer");
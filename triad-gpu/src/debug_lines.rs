@@ -0,0 +1,387 @@
+//! Immediate-mode debug line drawing: world axes, bounding boxes, frusta.
+//!
+//! Collect lines into a [`DebugLines`] batch each frame, upload it with
+//! [`DebugLineRenderer::upload`], and add [`DebugLineRenderer::pass`] to the
+//! frame graph after the scene passes. Lines are depth-tested against the scene
+//! but never write depth, so they overlay geometry without occluding it.
+
+use crate::builder::{BindingType, BufferUsage, DynamicBuffer, ShaderStage};
+use crate::error::{GpuError, RenderPassError};
+use crate::frame_graph::{Handle, PassBuilder};
+use crate::render::{ColorLoadOp, DepthLoadOp, DepthMode, RenderPassBuilder};
+use crate::resource_registry::ResourceRegistry;
+use crate::{FrameTextureView, Renderer};
+use glam::{Mat4, Vec3};
+
+const DEBUG_LINE_SHADER: &str = r#"
+struct DebugLineUniforms {
+    view_proj: mat4x4<f32>,
+};
+
+@group(0) @binding(0) var<uniform> u: DebugLineUniforms;
+
+struct VsIn {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec4<f32>,
+};
+
+struct VsOut {
+    @builtin(position) clip_pos: vec4<f32>,
+    @location(0) color: vec4<f32>,
+};
+
+@vertex
+fn vs_main(in: VsIn) -> VsOut {
+    var out: VsOut;
+    out.clip_pos = u.view_proj * vec4<f32>(in.position, 1.0);
+    out.color = in.color;
+    return out;
+}
+
+@fragment
+fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
+    return in.color;
+}
+"#;
+
+const DEBUG_LINE_ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+    wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x4];
+
+/// One endpoint of a debug line. Matches the vertex layout of the debug line shader.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct DebugLineVertex {
+    pub position: [f32; 3],
+    pub color: [f32; 4],
+}
+
+/// CPU-side batch of line segments, rebuilt every frame.
+#[derive(Debug, Clone, Default)]
+pub struct DebugLines {
+    vertices: Vec<DebugLineVertex>,
+}
+
+impl DebugLines {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn clear(&mut self) {
+        self.vertices.clear();
+    }
+
+    /// Number of line segments in the batch
+    pub fn len(&self) -> usize {
+        self.vertices.len() / 2
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Line-list vertices, two per segment
+    pub fn vertices(&self) -> &[DebugLineVertex] {
+        &self.vertices
+    }
+
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: [f32; 4]) -> &mut Self {
+        self.vertices.push(DebugLineVertex {
+            position: start.to_array(),
+            color,
+        });
+        self.vertices.push(DebugLineVertex {
+            position: end.to_array(),
+            color,
+        });
+        self
+    }
+
+    /// Red/green/blue segments along +X/+Y/+Z from `origin`.
+    pub fn axes(&mut self, origin: Vec3, length: f32) -> &mut Self {
+        self.line(origin, origin + Vec3::X * length, [1.0, 0.2, 0.2, 1.0]);
+        self.line(origin, origin + Vec3::Y * length, [0.2, 1.0, 0.2, 1.0]);
+        self.line(origin, origin + Vec3::Z * length, [0.2, 0.4, 1.0, 1.0]);
+        self
+    }
+
+    /// The 12 edges of an axis-aligned box.
+    pub fn aabb(&mut self, min: Vec3, max: Vec3, color: [f32; 4]) -> &mut Self {
+        let corners = [
+            Vec3::new(min.x, min.y, min.z),
+            Vec3::new(max.x, min.y, min.z),
+            Vec3::new(max.x, max.y, min.z),
+            Vec3::new(min.x, max.y, min.z),
+            Vec3::new(min.x, min.y, max.z),
+            Vec3::new(max.x, min.y, max.z),
+            Vec3::new(max.x, max.y, max.z),
+            Vec3::new(min.x, max.y, max.z),
+        ];
+        self.box_edges(&corners, color)
+    }
+
+    /// The 12 edges of the view frustum described by `view_proj`.
+    /// Uses wgpu clip space (depth in `0..=1`).
+    pub fn frustum(&mut self, view_proj: Mat4, color: [f32; 4]) -> &mut Self {
+        let inv = view_proj.inverse();
+        let corners = [
+            Vec3::new(-1.0, -1.0, 0.0),
+            Vec3::new(1.0, -1.0, 0.0),
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(-1.0, 1.0, 0.0),
+            Vec3::new(-1.0, -1.0, 1.0),
+            Vec3::new(1.0, -1.0, 1.0),
+            Vec3::new(1.0, 1.0, 1.0),
+            Vec3::new(-1.0, 1.0, 1.0),
+        ]
+        .map(|ndc| inv.project_point3(ndc));
+        self.box_edges(&corners, color)
+    }
+
+    /// Corners ordered as the near face (counter-clockwise) followed by the far face.
+    fn box_edges(&mut self, corners: &[Vec3; 8], color: [f32; 4]) -> &mut Self {
+        for i in 0..4 {
+            self.line(corners[i], corners[(i + 1) % 4], color);
+            self.line(corners[i + 4], corners[(i + 1) % 4 + 4], color);
+            self.line(corners[i], corners[i + 4], color);
+        }
+        self
+    }
+}
+
+/// GPU resources for drawing a [`DebugLines`] batch as a line list.
+pub struct DebugLineRenderer {
+    pipeline: Handle<wgpu::RenderPipeline>,
+    bind_group: Handle<wgpu::BindGroup>,
+    uniforms: Handle<wgpu::Buffer>,
+    vertices: DynamicBuffer<DebugLineVertex>,
+    has_depth: bool,
+}
+
+impl DebugLineRenderer {
+    /// Create the pipeline for `color_format`. Pass the scene depth format to
+    /// depth-test lines against it in `depth_mode`, or `None` to draw on top of everything.
+    pub fn new(
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        color_format: wgpu::TextureFormat,
+        depth_format: Option<wgpu::TextureFormat>,
+        depth_mode: DepthMode,
+    ) -> Result<Self, GpuError> {
+        let uniforms = renderer
+            .create_gpu_buffer::<[[f32; 4]; 4]>()
+            .label("debug lines uniforms")
+            .with_data(&[Mat4::IDENTITY.to_cols_array_2d()])
+            .usage(BufferUsage::Uniform)
+            .build(registry)?;
+
+        let vertices = renderer
+            .create_dynamic_buffer::<DebugLineVertex>()
+            .label("debug lines vertices")
+            .capacity(256)
+            .add_usage(wgpu::BufferUsages::VERTEX)
+            .build(registry)?;
+
+        let shader = renderer
+            .create_shader_module()
+            .label("debug lines")
+            .with_wgsl_source(DEBUG_LINE_SHADER)
+            .build(registry)?;
+
+        let (layout, bind_group) = renderer
+            .create_bind_group()
+            .label("debug lines")
+            .buffer_stage(
+                0,
+                ShaderStage::Vertex,
+                uniforms.handle(),
+                BindingType::Uniform,
+            )
+            .build(registry)?;
+
+        let pipeline_layout =
+            renderer
+                .device()
+                .create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+                    label: Some("debug lines layout"),
+                    bind_group_layouts: &[registry
+                        .get(layout)
+                        .ok_or(crate::error::BindGroupError::LayoutNotFound)?],
+                    push_constant_ranges: &[],
+                });
+
+        let mut pipeline = renderer
+            .create_render_pipeline()
+            .with_label("debug lines")
+            .with_vertex_shader(shader)
+            .with_fragment_shader(shader)
+            .with_layout(pipeline_layout)
+            .with_vertex_buffer(wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<DebugLineVertex>() as u64,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                attributes: &DEBUG_LINE_ATTRIBUTES,
+            })
+            .with_primitive(wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::LineList,
+                ..Default::default()
+            })
+            .with_fragment_target(Some(wgpu::ColorTargetState {
+                format: color_format,
+                blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                write_mask: wgpu::ColorWrites::ALL,
+            }));
+        if let Some(format) = depth_format {
+            pipeline =
+                pipeline
+                    .with_depth_mode(depth_mode)
+                    .with_depth_stencil(wgpu::DepthStencilState {
+                        format,
+                        depth_write_enabled: false,
+                        depth_compare: wgpu::CompareFunction::LessEqual,
+                        stencil: wgpu::StencilState::default(),
+                        bias: wgpu::DepthBiasState::default(),
+                    });
+        }
+        let pipeline = pipeline.build(registry)?;
+
+        Ok(Self {
+            pipeline,
+            bind_group,
+            uniforms: uniforms.handle(),
+            vertices,
+            has_depth: depth_format.is_some(),
+        })
+    }
+
+    /// Upload this frame's lines and camera. The vertex buffer grows as needed.
+    pub fn upload(
+        &mut self,
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        view_proj: Mat4,
+        lines: &DebugLines,
+    ) -> Result<(), GpuError> {
+        renderer.write_buffer(self.uniforms, &[view_proj.to_cols_array_2d()], registry)?;
+        self.vertices.clear();
+        self.vertices.append(renderer, registry, lines.vertices())?;
+        Ok(())
+    }
+
+    /// Number of vertices drawn by [`pass`](Self::pass)
+    pub fn vertex_count(&self) -> u32 {
+        self.vertices.len() as u32
+    }
+
+    /// Line pass that loads and draws over `color` (and `depth`, if the
+    /// renderer was created with a depth format).
+    pub fn pass(
+        &self,
+        name: impl Into<String>,
+        color: Handle<FrameTextureView>,
+        depth: Option<Handle<FrameTextureView>>,
+    ) -> Result<PassBuilder, RenderPassError> {
+        let mut builder = RenderPassBuilder::new(name)
+            .read(self.uniforms)
+            .read(self.vertices.buffer())
+            .with_pipeline(self.pipeline)
+            .with_bind_group(0, self.bind_group)
+            .with_vertex_buffer(0, self.vertices.buffer())
+            .with_frame_color_attachment(color, ColorLoadOp::Load);
+        if self.has_depth
+            && let Some(depth) = depth
+        {
+            builder = builder.with_frame_depth_stencil_attachment(
+                depth,
+                DepthLoadOp::Load,
+                wgpu::StoreOp::Store,
+                None,
+            );
+        }
+        builder.draw(self.vertex_count(), 1).build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FrameGraph;
+    use pollster::FutureExt;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_debug_lines_shapes() {
+        let mut lines = DebugLines::new();
+        lines.axes(Vec3::ZERO, 1.0);
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines.vertices()[1].position, [1.0, 0.0, 0.0]);
+
+        lines.aabb(Vec3::splat(-1.0), Vec3::splat(1.0), [1.0; 4]);
+        assert_eq!(lines.len(), 15);
+
+        lines.clear();
+        assert!(lines.is_empty());
+    }
+
+    #[test]
+    fn test_debug_lines_frustum_corners() {
+        let proj = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, 1.0, 10.0);
+        let mut lines = DebugLines::new();
+        lines.frustum(proj, [1.0; 4]);
+        assert_eq!(lines.len(), 12);
+
+        // First edge runs along the bottom of the near plane (z = -near).
+        let start = Vec3::from_array(lines.vertices()[0].position);
+        let end = Vec3::from_array(lines.vertices()[1].position);
+        assert!(start.abs_diff_eq(Vec3::new(-1.0, -1.0, -1.0), 1e-4));
+        assert!(end.abs_diff_eq(Vec3::new(1.0, -1.0, -1.0), 1e-4));
+    }
+
+    #[test]
+    fn test_debug_line_renderer_executes() {
+        let renderer = match Renderer::new().block_on() {
+            Ok(renderer) => renderer,
+            Err(err) => {
+                eprintln!("skipping debug line test: {err}");
+                return;
+            }
+        };
+        let mut registry = ResourceRegistry::default();
+        let format = wgpu::TextureFormat::Rgba8Unorm;
+        let mut debug =
+            DebugLineRenderer::new(&renderer, &mut registry, format, None, DepthMode::Standard)
+                .expect("debug line renderer");
+
+        let mut lines = DebugLines::new();
+        for i in 0..200 {
+            lines.axes(Vec3::splat(i as f32 * 0.01), 0.5);
+        }
+        debug
+            .upload(&renderer, &mut registry, Mat4::IDENTITY, &lines)
+            .expect("upload");
+        assert_eq!(debug.vertex_count(), 1200);
+
+        let texture = renderer.device().create_texture(&wgpu::TextureDescriptor {
+            label: Some("debug lines target"),
+            size: wgpu::Extent3d {
+                width: 16,
+                height: 16,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        });
+        let target = registry.insert(FrameTextureView::new());
+        registry
+            .get(target)
+            .expect("frame slot")
+            .set(Arc::new(texture.create_view(&Default::default())));
+
+        let mut graph = FrameGraph::new();
+        graph.add_pass(debug.pass("DebugLines", target, None).expect("pass"));
+        let mut executable = graph.build().expect("frame graph");
+        executable.execute(renderer.device(), renderer.queue(), &registry);
+    }
+}
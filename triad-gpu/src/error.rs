@@ -68,6 +68,11 @@ pub enum RendererError {
     #[error("failed to request GPU adapter: {0}")]
     RequestAdapter(#[from] wgpu::RequestAdapterError),
 
+    /// No adapter met the requested backends, features and limits.
+    /// `available` describes every adapter considered and why it was rejected.
+    #[error("no suitable GPU adapter found; available: [{}]", .available.join("; "))]
+    NoSuitableAdapter { available: Vec<String> },
+
     /// Failed to request a GPU device
     #[error("failed to request GPU device: {0}")]
     RequestDevice(#[from] wgpu::RequestDeviceError),
@@ -135,6 +140,10 @@ pub enum BufferError {
     /// DynamicBuffer capacity exceeded
     #[error("buffer capacity exceeded: requested {requested} elements but capacity is {capacity}")]
     CapacityExceeded { requested: usize, capacity: usize },
+
+    /// A wrapped DynamicBuffer ring can't be reordered on grow
+    #[error("cannot grow wrapped ring buffer: split at byte {offset} is not 4-byte aligned")]
+    UnalignedRingGrow { offset: u64 },
 }
 
 /// Errors that occur during bind group operations.
@@ -234,6 +243,10 @@ pub enum FrameGraphError {
     /// Circular dependency detected between passes
     #[error("circular dependency detected in frame graph")]
     CircularDependency,
+
+    /// A render pass could not be built while assembling the graph
+    #[error("failed to build render pass: {0}")]
+    RenderPass(#[from] RenderPassError),
 }
 
 /// Errors that occur while reading back GPU buffer contents to the CPU.
@@ -251,8 +264,8 @@ pub enum ReadbackError {
         element_size: usize,
     },
 
-    /// Waiting for buffer mapping callback failed
-    #[error("buffer map callback channel closed before completion")]
+    /// The buffer mapping callback never reported a result
+    #[error("buffer map callback did not report a result")]
     MapChannelClosed,
 
     /// Device polling failed
@@ -262,6 +275,18 @@ pub enum ReadbackError {
     /// Buffer mapping failed
     #[error("buffer map failed during readback: {0}")]
     Map(#[from] wgpu::BufferAsyncError),
+
+    /// Texture handle not found in registry
+    #[error("texture not found in registry")]
+    TextureNotFound,
+
+    /// Source buffer or texture was created without `COPY_SRC` usage
+    #[error("readback source is missing COPY_SRC usage")]
+    MissingCopySrc,
+
+    /// Texture format has no single-aspect, per-texel copy layout
+    #[error("texture format {0:?} cannot be read back texel by texel")]
+    UnsupportedTextureFormat(wgpu::TextureFormat),
 }
 
 /// Result type alias using the unified `GpuError`.
@@ -333,6 +358,12 @@ mod tests {
             GpuError::RenderPass(RenderPassError::MissingDraw)
         ));
 
+        let graph_err: FrameGraphError = RenderPassError::MissingPipeline.into();
+        assert!(matches!(
+            graph_err,
+            FrameGraphError::RenderPass(RenderPassError::MissingPipeline)
+        ));
+
         let copy_pass_err = CopyPassError::MissingCopy;
         let gpu_err: GpuError = copy_pass_err.into();
         assert!(matches!(
@@ -0,0 +1,311 @@
+//! Debug dumps of a built frame graph (Graphviz DOT and JSON).
+
+use crate::frame_graph::{ExecutableFrameGraph, HandleId};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+/// How a resource referenced by the graph was declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ResourceKind {
+    TransientBuffer,
+    TransientTexture,
+    Registered,
+    /// Accessed by a pass but never passed to `register_resource`.
+    Unregistered,
+}
+
+impl ResourceKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ResourceKind::TransientBuffer => "transient_buffer",
+            ResourceKind::TransientTexture => "transient_texture",
+            ResourceKind::Registered => "registered",
+            ResourceKind::Unregistered => "unregistered",
+        }
+    }
+}
+
+struct ResourceEntry<'a> {
+    kind: ResourceKind,
+    label: Option<&'a str>,
+}
+
+impl ExecutableFrameGraph {
+    /// Render the graph as a Graphviz `digraph`.
+    ///
+    /// Passes are boxes labelled with their declaration index and execution slot,
+    /// resources are ellipses (transients filled, unregistered ones dashed red),
+    /// and dashed grey edges chain passes in execution order.
+    pub fn to_dot(&self) -> String {
+        let resources = self.collect_resources();
+        let order = self.execution_slots();
+        let mut out = String::new();
+
+        out.push_str("digraph frame_graph {\n");
+        out.push_str("    rankdir=LR;\n");
+        out.push_str("    node [fontname=\"monospace\"];\n");
+
+        for (idx, pass) in self.passes.iter().enumerate() {
+            let slot = order
+                .get(&idx)
+                .map_or_else(|| "-".to_string(), |slot| slot.to_string());
+            let _ = writeln!(
+                out,
+                "    \"pass_{idx}\" [shape=box, label=\"{}\\n#{idx} order {slot}\"];",
+                escape_dot(pass.name())
+            );
+        }
+
+        for (id, entry) in &resources {
+            let label = match entry.label {
+                Some(label) => format!("{}\\n{id}", escape_dot(label)),
+                None => id.to_string(),
+            };
+            let style = match entry.kind {
+                ResourceKind::TransientBuffer | ResourceKind::TransientTexture => {
+                    ", style=filled, fillcolor=lightgrey"
+                }
+                ResourceKind::Registered => "",
+                ResourceKind::Unregistered => ", style=dashed, color=red",
+            };
+            let _ = writeln!(
+                out,
+                "    \"res_{id}\" [shape=ellipse, label=\"{label}\\n{}\"{style}];",
+                entry.kind.as_str()
+            );
+        }
+
+        for (idx, pass) in self.passes.iter().enumerate() {
+            for id in sorted(pass.reads()) {
+                let _ = writeln!(out, "    \"res_{id}\" -> \"pass_{idx}\" [label=\"read\"];");
+            }
+            for id in sorted(pass.writes()) {
+                let _ = writeln!(
+                    out,
+                    "    \"pass_{idx}\" -> \"res_{id}\" [label=\"write\", color=blue];"
+                );
+            }
+        }
+
+        for pair in self.execution_order.windows(2) {
+            let _ = writeln!(
+                out,
+                "    \"pass_{}\" -> \"pass_{}\" [style=dashed, color=grey, constraint=false];",
+                pair[0], pair[1]
+            );
+        }
+
+        out.push_str("}\n");
+        out
+    }
+
+    /// Serialize the graph structure as a JSON object with `passes`, `resources`,
+    /// `execution_order` and `surfaces` keys.
+    pub fn to_json(&self) -> String {
+        let resources = self.collect_resources();
+        let order = self.execution_slots();
+        let mut out = String::new();
+
+        out.push_str("{\"passes\":[");
+        for (idx, pass) in self.passes.iter().enumerate() {
+            if idx > 0 {
+                out.push(',');
+            }
+            let slot = order
+                .get(&idx)
+                .map_or_else(|| "null".to_string(), |slot| slot.to_string());
+            let _ = write!(
+                out,
+                "{{\"index\":{idx},\"name\":{},\"order\":{slot},\"reads\":{},\"writes\":{}}}",
+                json_string(pass.name()),
+                json_ids(sorted(pass.reads())),
+                json_ids(sorted(pass.writes())),
+            );
+        }
+
+        out.push_str("],\"resources\":[");
+        for (i, (id, entry)) in resources.iter().enumerate() {
+            if i > 0 {
+                out.push(',');
+            }
+            let label = entry.label.map_or_else(|| "null".to_string(), json_string);
+            let _ = write!(
+                out,
+                "{{\"id\":{id},\"kind\":\"{}\",\"label\":{label}}}",
+                entry.kind.as_str()
+            );
+        }
+
+        let _ = write!(
+            out,
+            "],\"execution_order\":{},\"surfaces\":{}}}",
+            json_ids(self.execution_order.iter().map(|&i| i as u64)),
+            json_ids(self.surface_handles.iter().copied()),
+        );
+        out
+    }
+
+    /// Map pass index -> position in the execution order.
+    fn execution_slots(&self) -> BTreeMap<usize, usize> {
+        self.execution_order
+            .iter()
+            .enumerate()
+            .map(|(slot, &idx)| (idx, slot))
+            .collect()
+    }
+
+    fn collect_resources(&self) -> BTreeMap<HandleId, ResourceEntry<'_>> {
+        let mut resources = BTreeMap::new();
+        for &id in &self.registered_resources {
+            resources.insert(
+                id,
+                ResourceEntry {
+                    kind: ResourceKind::Registered,
+                    label: None,
+                },
+            );
+        }
+        for (&id, desc) in &self.transient_buffers {
+            resources.insert(
+                id,
+                ResourceEntry {
+                    kind: ResourceKind::TransientBuffer,
+                    label: desc.label(),
+                },
+            );
+        }
+        for (&id, desc) in &self.transient_textures {
+            resources.insert(
+                id,
+                ResourceEntry {
+                    kind: ResourceKind::TransientTexture,
+                    label: desc.label(),
+                },
+            );
+        }
+        for pass in &self.passes {
+            for &id in pass.reads().iter().chain(pass.writes()) {
+                resources.entry(id).or_insert(ResourceEntry {
+                    kind: ResourceKind::Unregistered,
+                    label: None,
+                });
+            }
+        }
+        resources
+    }
+}
+
+fn sorted<'a>(ids: impl IntoIterator<Item = &'a HandleId>) -> Vec<HandleId> {
+    let mut ids: Vec<HandleId> = ids.into_iter().copied().collect();
+    ids.sort_unstable();
+    ids
+}
+
+fn json_ids(ids: impl IntoIterator<Item = u64>) -> String {
+    let ids: Vec<String> = ids.into_iter().map(|id| id.to_string()).collect();
+    format!("[{}]", ids.join(","))
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn escape_dot(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::frame_graph::{ExecutableFrameGraph, TransientBufferDesc};
+    use crate::frame_graph::{FrameGraph, Handle, Pass, PassBuilder, PassContext};
+
+    struct NoopPass(&'static str);
+
+    impl Pass for NoopPass {
+        fn name(&self) -> &str {
+            self.0
+        }
+
+        fn execute(&self, ctx: &PassContext) -> wgpu::CommandBuffer {
+            ctx.create_command_encoder(Some(self.0)).finish()
+        }
+    }
+
+    fn build_graph() -> (ExecutableFrameGraph, u64, u64, u64) {
+        let mut graph = FrameGraph::new();
+        let registered = Handle::<wgpu::Buffer>::next();
+        let unregistered = Handle::<wgpu::Texture>::next();
+        graph.register_resource(registered);
+        let scratch = graph.create_transient_buffer(
+            TransientBufferDesc::new(64, wgpu::BufferUsages::STORAGE).with_label("scratch"),
+        );
+
+        let mut producer = PassBuilder::new("produce");
+        producer.write(registered).write(scratch);
+        graph.add_pass(producer.with_pass(Box::new(NoopPass("produce"))));
+
+        let mut consumer = PassBuilder::new("consume \"final\"");
+        consumer.read(registered).read(scratch).write(unregistered);
+        graph.add_pass(consumer.with_pass(Box::new(NoopPass("consume"))));
+
+        let executable = graph.build().expect("graph should build");
+        (executable, registered.id(), scratch.id(), unregistered.id())
+    }
+
+    #[test]
+    fn test_to_dot_emits_passes_resources_and_edges() {
+        let (graph, registered, scratch, unregistered) = build_graph();
+        let dot = graph.to_dot();
+
+        assert!(dot.starts_with("digraph frame_graph {"));
+        assert!(dot.contains("\"pass_0\" [shape=box, label=\"produce\\n#0 order 0\"]"));
+        assert!(dot.contains("consume \\\"final\\\""));
+        assert!(dot.contains(&format!(
+            "\"pass_0\" -> \"res_{registered}\" [label=\"write\""
+        )));
+        assert!(dot.contains(&format!("\"res_{scratch}\" -> \"pass_1\" [label=\"read\"]")));
+        assert!(dot.contains(&format!("scratch\\n{scratch}\\ntransient_buffer")));
+        assert!(dot.contains(&format!(
+            "\"res_{unregistered}\" [shape=ellipse, label=\"{unregistered}\\nunregistered\", style=dashed, color=red]"
+        )));
+        assert!(dot.contains("\"pass_0\" -> \"pass_1\" [style=dashed"));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_to_json_reports_execution_order_and_kinds() {
+        let (graph, registered, scratch, unregistered) = build_graph();
+        let json = graph.to_json();
+
+        assert!(json.contains(&format!(
+            "{{\"index\":0,\"name\":\"produce\",\"order\":0,\"reads\":[],\"writes\":[{registered},{scratch}]}}"
+        )));
+        assert!(json.contains("\"name\":\"consume \\\"final\\\"\",\"order\":1"));
+        assert!(json.contains(&format!(
+            "{{\"id\":{registered},\"kind\":\"registered\",\"label\":null}}"
+        )));
+        assert!(json.contains(&format!(
+            "{{\"id\":{scratch},\"kind\":\"transient_buffer\",\"label\":\"scratch\"}}"
+        )));
+        assert!(json.contains(&format!(
+            "{{\"id\":{unregistered},\"kind\":\"unregistered\",\"label\":null}}"
+        )));
+        assert!(json.ends_with("\"execution_order\":[0,1],\"surfaces\":[]}"));
+    }
+}
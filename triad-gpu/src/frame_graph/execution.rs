@@ -24,8 +24,8 @@ pub fn topological_sort(passes: &[PassNode]) -> Result<Vec<usize>, FrameGraphErr
 
     // Kahn's algorithm
     let mut queue = VecDeque::new();
-    for i in 0..n {
-        if in_degree[i] == 0 {
+    for (i, &degree) in in_degree.iter().enumerate() {
+        if degree == 0 {
             queue.push_back(i);
         }
     }
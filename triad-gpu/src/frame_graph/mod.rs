@@ -1,3 +1,4 @@
+mod dump;
 mod execution;
 pub mod pass;
 pub mod resource;
@@ -6,7 +7,7 @@ use crate::error::FrameGraphError;
 use crate::frame_graph::pass::PassNode;
 use crate::frame_graph::resource::{ResourceInfo, ResourceState};
 use crate::resource_registry::ResourceRegistry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tracing::{debug_span, instrument};
 
 pub use pass::{Pass, PassBuilder, PassContext};
@@ -18,6 +19,7 @@ pub type SurfaceId = u64;
 pub struct FrameGraph {
     passes: Vec<PassNode>,
     resource_info: HashMap<HandleId, ResourceInfo>,
+    registered_resources: HashSet<HandleId>, // Explicitly registered, for debug dumps
     transient_buffers: HashMap<HandleId, TransientBufferDesc>,
     transient_textures: HashMap<HandleId, TransientTextureDesc>,
     surface_handles: Vec<SurfaceId>, // Surface handle IDs (surfaces tracked separately)
@@ -37,17 +39,14 @@ impl FrameGraph {
     }
 
     pub fn register_resource<T: ResourceType>(&mut self, handle: Handle<T>) -> &mut Self {
-        self.resource_info
-            .entry(handle.id())
-            .or_insert_with(ResourceInfo::new);
+        self.resource_info.entry(handle.id()).or_default();
+        self.registered_resources.insert(handle.id());
         self
     }
 
     pub fn create_transient_buffer(&mut self, desc: TransientBufferDesc) -> Handle<wgpu::Buffer> {
         let handle = Handle::next();
-        self.resource_info
-            .entry(handle.id())
-            .or_insert_with(ResourceInfo::new);
+        self.resource_info.entry(handle.id()).or_default();
         self.transient_buffers.insert(handle.id(), desc);
         handle
     }
@@ -57,9 +56,7 @@ impl FrameGraph {
         desc: TransientTextureDesc,
     ) -> Handle<wgpu::Texture> {
         let handle = Handle::next();
-        self.resource_info
-            .entry(handle.id())
-            .or_insert_with(ResourceInfo::new);
+        self.resource_info.entry(handle.id()).or_default();
         self.transient_textures.insert(handle.id(), desc);
         handle
     }
@@ -69,18 +66,12 @@ impl FrameGraph {
 
         // Update resource tracking - accumulate access patterns rather than overwriting
         for read_id in pass.reads() {
-            let info = self
-                .resource_info
-                .entry(*read_id)
-                .or_insert_with(ResourceInfo::new);
+            let info = self.resource_info.entry(*read_id).or_default();
             info.set_state(info.state().merge_with(ResourceState::Read));
         }
 
         for write_id in pass.writes() {
-            let info = self
-                .resource_info
-                .entry(*write_id)
-                .or_insert_with(ResourceInfo::new);
+            let info = self.resource_info.entry(*write_id).or_default();
             info.set_state(info.state().merge_with(ResourceState::Write));
         }
 
@@ -141,6 +132,7 @@ impl FrameGraph {
         Ok(ExecutableFrameGraph {
             passes: self.passes,
             execution_order,
+            registered_resources: self.registered_resources,
             transient_buffers: self.transient_buffers,
             transient_textures: self.transient_textures,
             surface_handles: self.surface_handles,
@@ -152,6 +144,7 @@ impl FrameGraph {
 pub struct ExecutableFrameGraph {
     passes: Vec<PassNode>,
     execution_order: Vec<usize>,
+    registered_resources: HashSet<HandleId>,
     transient_buffers: HashMap<HandleId, TransientBufferDesc>,
     transient_textures: HashMap<HandleId, TransientTextureDesc>,
     surface_handles: Vec<u64>, // Surface handle IDs (surfaces tracked separately)
@@ -294,6 +287,7 @@ mod tests {
     use pollster::FutureExt;
 
     // Mock pass for testing
+    #[allow(dead_code)]
     struct MockPass {
         name: String,
         reads: Vec<u64>,
@@ -322,8 +316,16 @@ mod tests {
             .register_resource(texture_handle);
 
         // Resources should be registered
-        assert!(frame_graph.resource_info.contains_key(&buffer_handle.id()));
-        assert!(frame_graph.resource_info.contains_key(&texture_handle.id()));
+        assert!(
+            frame_graph
+                .resource_info()
+                .contains_key(&buffer_handle.id())
+        );
+        assert!(
+            frame_graph
+                .resource_info()
+                .contains_key(&texture_handle.id())
+        );
     }
 
     #[test]
@@ -334,7 +336,7 @@ mod tests {
                 .with_label("transient_scratch"),
         );
 
-        assert!(frame_graph.resource_info.contains_key(&handle.id()));
+        assert!(frame_graph.resource_info().contains_key(&handle.id()));
         let desc = frame_graph
             .transient_buffers
             .get(&handle.id())
@@ -359,7 +361,7 @@ mod tests {
             .with_label("transient_color"),
         );
 
-        assert!(frame_graph.resource_info.contains_key(&handle.id()));
+        assert!(frame_graph.resource_info().contains_key(&handle.id()));
         let desc = frame_graph
             .transient_textures
             .get(&handle.id())
@@ -6,19 +6,24 @@
 //! Asset loading, file watching, and user-facing shader workflows belong in
 //! higher-level crates.
 
-use wgpu::{Instance, SurfaceConfiguration};
+use wgpu::SurfaceConfiguration;
+mod background;
 mod builder;
 mod compute;
 mod copy;
+mod debug_lines;
 pub mod error;
 mod frame_graph;
 mod frame_slot;
 mod indirect;
 mod pipeline;
+mod readback;
 #[cfg(test)]
 mod reference_pipeline;
 mod render;
+mod renderer_builder;
 mod resource_registry;
+mod scan;
 mod spatial_grid;
 mod surface;
 #[cfg(test)]
@@ -32,13 +37,15 @@ pub use error::{
     TextureError,
 };
 
+pub use background::{BackgroundConfig, BackgroundRenderer, GroundGrid, SkyGradient};
 pub use builder::{
     BindGroupBuilder, BindingType, BufferBuilder, BufferUsage, ComputePipelineBuilder,
     DynamicBuffer, DynamicBufferBuilder, GpuBuffer, GpuBufferBuilder, ShaderModuleBuilder,
     ShaderSource, ShaderStage, TextureBuilder, TextureViewBuilder,
 };
-pub use compute::{ComputeDispatch, ComputePassBuilder};
+pub use compute::{ComputeDispatch, ComputePassBuilder, workgroup_count};
 pub use copy::{BufferCopy, CopyPassBuilder, TextureBufferCopy, TextureCopy};
+pub use debug_lines::{DebugLineRenderer, DebugLineVertex, DebugLines};
 pub use frame_graph::{
     ExecutableFrameGraph, FrameGraph, Handle, Pass, PassBuilder, PassContext, ResourceType,
     TransientBufferDesc, TransientTextureDesc,
@@ -46,15 +53,24 @@ pub use frame_graph::{
 pub use frame_slot::{FrameBufferHandle, FrameTextureView};
 pub use indirect::{DispatchIndirectArgs, DrawIndexedIndirectArgs, DrawIndirectArgs};
 pub use pipeline::RenderPipelineBuilder;
-pub use render::{ColorLoadOp, DepthLoadOp, RenderDraw, RenderPassBuilder};
+pub use readback::{Download, padded_bytes_per_row};
+pub use render::{ColorLoadOp, DepthLoadOp, DepthMode, RenderDraw, RenderPassBuilder};
+pub use renderer_builder::RendererBuilder;
 pub use resource_registry::ResourceRegistry;
+pub use scan::{
+    GpuReduce, GpuScan, ReduceOp, SCAN_WORKGROUP_SIZE, ScanError, ScanLevel, ScanResult,
+    scan_levels,
+};
 pub use spatial_grid::{
     EntityPosition, SpatialGridConfig, SpatialGridError, SpatialGridGpu, SpatialGridParams,
     SpatialGridResult, total_cells,
 };
-pub use surface::SurfaceWrapper;
+pub use surface::{PresentModePreference, SurfaceWrapper};
 pub use wgpu;
 
+/// `desired_maximum_frame_latency` used when the caller does not pick one.
+pub const DEFAULT_MAX_FRAME_LATENCY: u32 = 2;
+
 /// Prefer stable vsync-capable modes; only use [`wgpu::PresentMode::Immediate`] if nothing else is available.
 #[must_use]
 fn pick_default_present_mode(modes: &[wgpu::PresentMode]) -> wgpu::PresentMode {
@@ -85,29 +101,14 @@ pub struct Renderer {
 }
 
 impl Renderer {
+    /// Create a renderer on the default adapter with default limits.
+    /// Use [`Renderer::builder`] to choose the adapter, backend, features or limits.
     pub async fn new() -> std::result::Result<Self, RendererError> {
-        #[cfg(test)]
-        let _gpu_test_guard = crate::test_util::gpu_test_lock();
-
-        let instance = Instance::new(&wgpu::InstanceDescriptor::from_env_or_default());
-
-        let adapter = instance
-            .request_adapter(&wgpu::RequestAdapterOptions::default())
-            .await?;
-
-        let (device, queue) = adapter
-            .request_device(&wgpu::DeviceDescriptor {
-                label: Some("Renderer"),
-                ..Default::default()
-            })
-            .await?;
-
-        Ok(Self {
-            device,
-            queue,
-            instance,
-            adapter,
-        })
+        RendererBuilder::new().build().await
+    }
+
+    pub fn builder() -> RendererBuilder {
+        RendererBuilder::new()
     }
 
     #[cfg(test)]
@@ -131,6 +132,16 @@ impl Renderer {
         &self.instance
     }
 
+    /// Get a reference to the adapter the device was created on
+    pub fn adapter(&self) -> &wgpu::Adapter {
+        &self.adapter
+    }
+
+    /// Name, backend and device type of the selected adapter
+    pub fn adapter_info(&self) -> wgpu::AdapterInfo {
+        self.adapter.get_info()
+    }
+
     /// Create a buffer builder for constructing GPU buffers
     pub fn create_buffer(&self) -> BufferBuilder<'_> {
         BufferBuilder::new(&self.device)
@@ -248,36 +259,53 @@ impl Renderer {
         buffer: Handle<wgpu::Buffer>,
         registry: &ResourceRegistry,
     ) -> std::result::Result<Vec<T>, ReadbackError> {
-        use std::sync::mpsc;
-
         let buffer_ref = registry.get(buffer).ok_or(ReadbackError::BufferNotFound)?;
         let buffer_size = buffer_ref.size();
         let element_size = std::mem::size_of::<T>();
 
-        if buffer_size as usize % element_size != 0 {
+        if !(buffer_size as usize).is_multiple_of(element_size) {
             return Err(ReadbackError::BufferSizeNotAligned {
                 buffer_size,
                 element_size,
             });
         }
 
-        let slice = buffer_ref.slice(..);
-        let (tx, rx) = mpsc::channel();
-        slice.map_async(wgpu::MapMode::Read, move |result| {
-            let _ = tx.send(result);
-        });
-
-        self.device.poll(wgpu::PollType::wait_indefinitely())?;
-        let map_result = rx.recv().map_err(|_| ReadbackError::MapChannelClosed)?;
-        map_result?;
+        let bytes = Download::mappable(&self.device, buffer_ref).wait()?;
+        let data = bytes
+            .chunks_exact(element_size)
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+        Ok(data)
+    }
 
-        let data = {
-            let mapped = slice.get_mapped_range();
-            bytemuck::cast_slice::<u8, T>(&mapped).to_vec()
-        };
-        buffer_ref.unmap();
+    /// Start downloading the full contents of a `COPY_SRC` buffer without blocking.
+    ///
+    /// The copy into an internal staging buffer is submitted immediately, so the
+    /// result reflects all work submitted before this call.
+    pub fn read_buffer_async(
+        &self,
+        buffer: Handle<wgpu::Buffer>,
+        registry: &ResourceRegistry,
+    ) -> Download {
+        match registry.get(buffer) {
+            Some(source) => Download::buffer(&self.device, &self.queue, source),
+            None => Download::failed(&self.device, ReadbackError::BufferNotFound),
+        }
+    }
 
-        Ok(data)
+    /// Start downloading mip 0, layer 0 of a `COPY_SRC` texture without blocking.
+    ///
+    /// Rows are copied with 256-byte alignment and returned tightly packed
+    /// (`width * bytes_per_texel` per row).
+    pub fn read_texture_async(
+        &self,
+        texture: Handle<wgpu::Texture>,
+        registry: &ResourceRegistry,
+    ) -> Download {
+        match registry.get(texture) {
+            Some(source) => Download::texture(&self.device, &self.queue, source),
+            None => Download::failed(&self.device, ReadbackError::TextureNotFound),
+        }
     }
 
     pub fn create_surface(
@@ -286,58 +314,68 @@ impl Renderer {
         width: u32,
         height: u32,
     ) -> std::result::Result<SurfaceWrapper, RendererError> {
-        // Validate width and height are non-zero
-        if width == 0 || height == 0 {
-            return Err(RendererError::InvalidDimensions { width, height });
-        }
-
-        let caps = surface.get_capabilities(&self.adapter);
-
-        // Check if formats array is empty
-        if caps.formats.is_empty() {
-            return Err(RendererError::NoSupportedFormats);
-        }
-
-        let format = caps
-            .formats
-            .iter()
-            .copied()
-            .find(|f| f.is_srgb())
-            .unwrap_or(caps.formats[0]);
-
-        // Check if present_modes array is empty
-        if caps.present_modes.is_empty() {
-            return Err(RendererError::NoSupportedPresentModes);
-        }
-
-        let present_mode = pick_default_present_mode(&caps.present_modes);
-
-        // Check if alpha_modes array is empty
-        if caps.alpha_modes.is_empty() {
-            return Err(RendererError::NoSupportedAlphaModes);
-        }
+        self.configure_surface(
+            surface,
+            width,
+            height,
+            DEFAULT_MAX_FRAME_LATENCY,
+            pick_default_present_mode,
+        )
+    }
 
-        let config = SurfaceConfiguration {
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
-            format,
+    /// Create a surface with an explicit present mode.
+    /// Unsupported modes fall back to a supported one; check [`SurfaceWrapper::present_mode`].
+    pub fn create_surface_with_mode(
+        &self,
+        surface: wgpu::Surface<'static>,
+        width: u32,
+        height: u32,
+        present_mode: wgpu::PresentMode,
+    ) -> std::result::Result<SurfaceWrapper, RendererError> {
+        self.create_surface_with_latency(
+            surface,
             width,
             height,
             present_mode,
-            alpha_mode: caps.alpha_modes[0],
-            view_formats: vec![],
-            desired_maximum_frame_latency: 2,
-        };
-
-        surface.configure(&self.device, &config);
-        Ok(SurfaceWrapper::new(surface, config))
+            DEFAULT_MAX_FRAME_LATENCY,
+        )
     }
 
-    pub fn create_surface_with_mode(
+    /// Create a surface with an explicit present mode and
+    /// `desired_maximum_frame_latency`.
+    pub fn create_surface_with_latency(
         &self,
         surface: wgpu::Surface<'static>,
         width: u32,
         height: u32,
         present_mode: wgpu::PresentMode,
+        max_frame_latency: u32,
+    ) -> std::result::Result<SurfaceWrapper, RendererError> {
+        self.configure_surface(surface, width, height, max_frame_latency, |modes| {
+            surface::resolve_present_mode(present_mode, modes)
+        })
+    }
+
+    /// Create a surface using the best supported mode for `preference`.
+    pub fn create_surface_with_preference(
+        &self,
+        surface: wgpu::Surface<'static>,
+        width: u32,
+        height: u32,
+        preference: PresentModePreference,
+    ) -> std::result::Result<SurfaceWrapper, RendererError> {
+        self.configure_surface(surface, width, height, DEFAULT_MAX_FRAME_LATENCY, |modes| {
+            preference.resolve(modes)
+        })
+    }
+
+    fn configure_surface(
+        &self,
+        surface: wgpu::Surface<'static>,
+        width: u32,
+        height: u32,
+        max_frame_latency: u32,
+        pick_present_mode: impl FnOnce(&[wgpu::PresentMode]) -> wgpu::PresentMode,
     ) -> std::result::Result<SurfaceWrapper, RendererError> {
         // Validate width and height are non-zero
         if width == 0 || height == 0 {
@@ -358,6 +396,13 @@ impl Renderer {
             .find(|f| f.is_srgb())
             .unwrap_or(caps.formats[0]);
 
+        // Check if present_modes array is empty
+        if caps.present_modes.is_empty() {
+            return Err(RendererError::NoSupportedPresentModes);
+        }
+
+        let present_mode = pick_present_mode(&caps.present_modes);
+
         // Check if alpha_modes array is empty
         if caps.alpha_modes.is_empty() {
             return Err(RendererError::NoSupportedAlphaModes);
@@ -371,11 +416,11 @@ impl Renderer {
             present_mode,
             alpha_mode: caps.alpha_modes[0],
             view_formats: vec![],
-            desired_maximum_frame_latency: 2,
+            desired_maximum_frame_latency: max_frame_latency.max(1),
         };
 
         surface.configure(&self.device, &config);
-        Ok(SurfaceWrapper::new(surface, config))
+        Ok(SurfaceWrapper::new(surface, config, caps.present_modes))
     }
 }
 
@@ -747,7 +792,7 @@ mod tests {
         }
 
         let initial = vec![TestElement::default(); 10];
-        let mut buf: DynamicBuffer<TestElement> = renderer
+        let buf: DynamicBuffer<TestElement> = renderer
             .create_dynamic_buffer()
             .with_data(&initial)
             .build(&mut registry)
@@ -917,6 +962,157 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_dynamic_buffer_append_grows_and_keeps_handle() {
+        let renderer = Renderer::new()
+            .block_on()
+            .expect("Failed to create renderer");
+        let mut registry = ResourceRegistry::default();
+
+        let mut buf: DynamicBuffer<u32> = renderer
+            .create_dynamic_buffer()
+            .label("test_append")
+            .capacity(4)
+            .build(&mut registry)
+            .expect("Failed to create dynamic buffer");
+        let handle = buf.buffer();
+
+        let first = [1u32, 2, 3];
+        assert_eq!(buf.append(&renderer, &mut registry, &first).unwrap(), 0);
+        assert_eq!(buf.generation(), 0);
+
+        let second = [4u32, 5, 6, 7, 8];
+        assert_eq!(buf.append(&renderer, &mut registry, &second).unwrap(), 3);
+        assert_eq!(buf.buffer(), handle);
+        assert_eq!(buf.capacity(), 8);
+        assert_eq!(buf.len(), 8);
+        assert_eq!(buf.generation(), 1);
+
+        let readback = renderer
+            .create_gpu_buffer::<u32>()
+            .label("append_readback")
+            .capacity(8)
+            .usage(BufferUsage::Readback)
+            .build(&mut registry)
+            .expect("readback buffer");
+        let pass = renderer
+            .create_copy_pass("AppendReadback")
+            .copy_buffer(handle, readback.handle(), 32)
+            .build()
+            .expect("copy pass");
+        let mut graph = FrameGraph::new();
+        graph.add_pass(pass);
+        let mut executable = graph.build().expect("frame graph");
+        let command_buffers =
+            executable.execute_no_submit(renderer.device(), renderer.queue(), &registry);
+        renderer.queue().submit(command_buffers);
+
+        let contents = renderer
+            .read_buffer::<u32>(readback.handle(), &registry)
+            .expect("readback");
+        assert_eq!(contents, [1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    fn read_dynamic(
+        renderer: &Renderer,
+        registry: &ResourceRegistry,
+        buf: &DynamicBuffer<u32>,
+    ) -> Vec<u32> {
+        let bytes = renderer
+            .read_buffer_async(buf.buffer(), registry)
+            .wait()
+            .expect("readback");
+        bytes
+            .chunks_exact(4)
+            .map(bytemuck::pod_read_unaligned)
+            .collect()
+    }
+
+    #[test]
+    fn test_dynamic_buffer_push_ring_wraps() {
+        let renderer = Renderer::new()
+            .block_on()
+            .expect("Failed to create renderer");
+        let mut registry = ResourceRegistry::default();
+
+        let mut buf: DynamicBuffer<u32> = renderer
+            .create_dynamic_buffer()
+            .label("test_ring")
+            .capacity(4)
+            .build(&mut registry)
+            .expect("Failed to create dynamic buffer");
+
+        assert_eq!(buf.push_ring(&renderer, &registry, &[1, 2, 3]).unwrap(), 0);
+        assert_eq!(read_dynamic(&renderer, &registry, &buf)[..3], [1, 2, 3]);
+        assert_eq!(buf.push_ring(&renderer, &registry, &[4, 5]).unwrap(), 3);
+        assert_eq!(buf.len(), 4);
+        assert_eq!(buf.capacity(), 4);
+        assert_eq!(read_dynamic(&renderer, &registry, &buf), [5, 2, 3, 4]);
+        // Oversized pushes keep only the newest `capacity` elements.
+        assert_eq!(
+            buf.push_ring(&renderer, &registry, &[6, 7, 8, 9, 10])
+                .unwrap(),
+            1
+        );
+        assert_eq!(read_dynamic(&renderer, &registry, &buf), [10, 7, 8, 9]);
+        assert_eq!(buf.generation(), 0);
+    }
+
+    #[test]
+    fn test_dynamic_buffer_push_then_ring_keeps_live_elements() {
+        let renderer = Renderer::new()
+            .block_on()
+            .expect("Failed to create renderer");
+        let mut registry = ResourceRegistry::default();
+
+        let mut buf: DynamicBuffer<u32> = renderer
+            .create_dynamic_buffer()
+            .label("test_ring_mixed")
+            .capacity(4)
+            .build(&mut registry)
+            .expect("Failed to create dynamic buffer");
+
+        assert_eq!(buf.push_ring(&renderer, &registry, &[1]).unwrap(), 0);
+        assert_eq!(buf.push(&renderer, &registry, &[2, 3]).unwrap(), 1);
+        // The ring continues after the pushed elements instead of overwriting them.
+        assert_eq!(buf.push_ring(&renderer, &registry, &[4]).unwrap(), 3);
+        assert_eq!(read_dynamic(&renderer, &registry, &buf), [1, 2, 3, 4]);
+        assert_eq!(buf.push_ring(&renderer, &registry, &[5]).unwrap(), 0);
+        assert_eq!(read_dynamic(&renderer, &registry, &buf), [5, 2, 3, 4]);
+
+        // Growing a wrapped ring moves the oldest element to index 0 and appends after it.
+        assert_eq!(buf.append(&renderer, &mut registry, &[6]).unwrap(), 4);
+        assert_eq!(buf.push_ring(&renderer, &registry, &[7, 8, 9]).unwrap(), 5);
+        assert_eq!(
+            read_dynamic(&renderer, &registry, &buf),
+            [2, 3, 4, 5, 6, 7, 8, 9]
+        );
+        // Once full again, the ring overwrites the oldest element first.
+        assert_eq!(buf.push_ring(&renderer, &registry, &[10]).unwrap(), 0);
+        assert_eq!(
+            read_dynamic(&renderer, &registry, &buf),
+            [10, 3, 4, 5, 6, 7, 8, 9]
+        );
+    }
+
+    #[test]
+    fn test_dynamic_buffer_push_ring_empty() {
+        let renderer = Renderer::new()
+            .block_on()
+            .expect("Failed to create renderer");
+        let mut registry = ResourceRegistry::default();
+
+        let mut buf: DynamicBuffer<u32> = renderer
+            .create_dynamic_buffer()
+            .label("test_ring_empty")
+            .capacity(0)
+            .build(&mut registry)
+            .expect("Failed to create dynamic buffer");
+
+        assert_eq!(buf.push_ring(&renderer, &registry, &[]).unwrap(), 0);
+        assert!(buf.push_ring(&renderer, &registry, &[1]).is_err());
+    }
+
     #[test]
     fn test_storage_writable_usage() {
         let renderer = Renderer::new()
@@ -1,5 +1,6 @@
 use crate::error::PipelineError;
 use crate::frame_graph::resource::Handle;
+use crate::render::DepthMode;
 use crate::resource_registry::ResourceRegistry;
 
 /// Builder for creating render pipelines
@@ -12,6 +13,7 @@ pub struct RenderPipelineBuilder<'a> {
     vertex_buffers: Vec<wgpu::VertexBufferLayout<'static>>,
     primitive: Option<wgpu::PrimitiveState>,
     depth_stencil: Option<wgpu::DepthStencilState>,
+    depth_mode: DepthMode,
     multisample: Option<wgpu::MultisampleState>,
     fragment_targets: Vec<Option<wgpu::ColorTargetState>>,
 }
@@ -27,6 +29,7 @@ impl<'a> RenderPipelineBuilder<'a> {
             vertex_buffers: Vec::new(),
             primitive: None,
             depth_stencil: None,
+            depth_mode: DepthMode::Standard,
             multisample: None,
             fragment_targets: Vec::new(),
         }
@@ -67,6 +70,13 @@ impl<'a> RenderPipelineBuilder<'a> {
         self
     }
 
+    /// Depth convention of the target. The depth-stencil state is written for
+    /// standard depth and its compare function is flipped at build for `Reversed`.
+    pub fn with_depth_mode(mut self, depth_mode: DepthMode) -> Self {
+        self.depth_mode = depth_mode;
+        self
+    }
+
     pub fn with_multisample(mut self, multisample: wgpu::MultisampleState) -> Self {
         self.multisample = Some(multisample);
         self
@@ -115,7 +125,7 @@ impl<'a> RenderPipelineBuilder<'a> {
                     compilation_options: wgpu::PipelineCompilationOptions::default(),
                     buffers: &self.vertex_buffers,
                 },
-                primitive: self.primitive.unwrap_or_else(|| wgpu::PrimitiveState {
+                primitive: self.primitive.unwrap_or(wgpu::PrimitiveState {
                     topology: wgpu::PrimitiveTopology::TriangleList,
                     strip_index_format: None,
                     front_face: wgpu::FrontFace::Ccw,
@@ -124,8 +134,11 @@ impl<'a> RenderPipelineBuilder<'a> {
                     polygon_mode: wgpu::PolygonMode::Fill,
                     conservative: false,
                 }),
-                depth_stencil: self.depth_stencil,
-                multisample: self.multisample.unwrap_or_else(|| wgpu::MultisampleState {
+                depth_stencil: self.depth_stencil.map(|state| wgpu::DepthStencilState {
+                    depth_compare: self.depth_mode.compare(state.depth_compare),
+                    ..state
+                }),
+                multisample: self.multisample.unwrap_or(wgpu::MultisampleState {
                     count: 1,
                     mask: !0,
                     alpha_to_coverage_enabled: false,
@@ -0,0 +1,461 @@
+//! Non-blocking GPU → CPU downloads through internal staging buffers.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+use crate::error::ReadbackError;
+
+/// Map result plus the task to wake when it lands, shared with the `map_async` callback.
+#[derive(Default)]
+struct MapState {
+    result: Option<Result<(), wgpu::BufferAsyncError>>,
+    waker: Option<Waker>,
+}
+
+type MapStatus = Arc<Mutex<MapState>>;
+
+/// Bytes per row of a texture copy, padded to `wgpu::COPY_BYTES_PER_ROW_ALIGNMENT` (256).
+#[inline]
+pub const fn padded_bytes_per_row(width: u32, bytes_per_texel: u32) -> u32 {
+    let unpadded = width * bytes_per_texel;
+    let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+    unpadded.div_ceil(align) * align
+}
+
+/// Row padding to strip from a mapped texture copy.
+#[derive(Debug, Clone, Copy)]
+struct RowLayout {
+    padded_bytes_per_row: u32,
+    bytes_per_row: u32,
+    rows: u32,
+}
+
+enum DownloadState {
+    Failed(ReadbackError),
+    Mapping {
+        staging: wgpu::Buffer,
+        status: MapStatus,
+        rows: Option<RowLayout>,
+    },
+    Finished,
+}
+
+/// A pending download started by [`crate::Renderer::read_buffer_async`] or
+/// [`crate::Renderer::read_texture_async`].
+///
+/// Await it from any executor, or call [`Download::try_finish`] once per frame from a
+/// render loop. Both poll the device once without blocking. An awaiting task is woken
+/// by the map callback, which wgpu runs from later device polls and queue submits, so
+/// something else must keep the device moving (a render loop, or
+/// [`Download::wait`] when blocking is fine).
+pub struct Download {
+    device: wgpu::Device,
+    state: DownloadState,
+}
+
+impl Download {
+    pub(crate) fn failed(device: &wgpu::Device, error: ReadbackError) -> Self {
+        Self {
+            device: device.clone(),
+            state: DownloadState::Failed(error),
+        }
+    }
+
+    /// Start mapping `staging`, whose copy has already been submitted.
+    fn map(device: &wgpu::Device, staging: wgpu::Buffer, rows: Option<RowLayout>) -> Self {
+        let status = MapStatus::default();
+        let status_for_callback = Arc::clone(&status);
+        staging
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| {
+                let waker = match status_for_callback.lock() {
+                    Ok(mut state) => {
+                        state.result = Some(result);
+                        state.waker.take()
+                    }
+                    Err(_) => None,
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            });
+        Self {
+            device: device.clone(),
+            state: DownloadState::Mapping {
+                staging,
+                status,
+                rows,
+            },
+        }
+    }
+
+    /// Map a `MAP_READ` buffer in place; it is unmapped again once the data is copied out.
+    pub(crate) fn mappable(device: &wgpu::Device, buffer: &wgpu::Buffer) -> Self {
+        Self::map(device, buffer.clone(), None)
+    }
+
+    /// Copy all of `source` into a fresh staging buffer and start mapping it.
+    pub(crate) fn buffer(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        source: &wgpu::Buffer,
+    ) -> Self {
+        if !source.usage().contains(wgpu::BufferUsages::COPY_SRC) {
+            return Self::failed(device, ReadbackError::MissingCopySrc);
+        }
+        let size = source.size();
+        if !size.is_multiple_of(wgpu::COPY_BUFFER_ALIGNMENT) {
+            return Self::failed(
+                device,
+                ReadbackError::BufferSizeNotAligned {
+                    buffer_size: size,
+                    element_size: wgpu::COPY_BUFFER_ALIGNMENT as usize,
+                },
+            );
+        }
+
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback staging"),
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("readback buffer copy"),
+        });
+        encoder.copy_buffer_to_buffer(source, 0, &staging, 0, size);
+        queue.submit([encoder.finish()]);
+        Self::map(device, staging, None)
+    }
+
+    /// Copy mip 0, layer 0 of `source` into a row-aligned staging buffer and start mapping it.
+    pub(crate) fn texture(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        source: &wgpu::Texture,
+    ) -> Self {
+        if !source.usage().contains(wgpu::TextureUsages::COPY_SRC) {
+            return Self::failed(device, ReadbackError::MissingCopySrc);
+        }
+        let format = source.format();
+        let bytes_per_texel = match (format.block_copy_size(None), format.block_dimensions()) {
+            (Some(size), (1, 1)) => size,
+            _ => return Self::failed(device, ReadbackError::UnsupportedTextureFormat(format)),
+        };
+
+        let (width, height) = (source.width(), source.height());
+        let rows = RowLayout {
+            padded_bytes_per_row: padded_bytes_per_row(width, bytes_per_texel),
+            bytes_per_row: width * bytes_per_texel,
+            rows: height,
+        };
+        let staging = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("readback texture staging"),
+            size: u64::from(rows.padded_bytes_per_row) * u64::from(height),
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor {
+            label: Some("readback texture copy"),
+        });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: source,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &staging,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(rows.padded_bytes_per_row),
+                    rows_per_image: Some(height),
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        queue.submit([encoder.finish()]);
+        Self::map(device, staging, Some(rows))
+    }
+
+    /// Poll the device once without blocking and return the data if the map has completed.
+    ///
+    /// Returns `None` while the download is in flight, and again after the result has
+    /// been taken.
+    pub fn try_finish(&mut self) -> Option<Result<Vec<u8>, ReadbackError>> {
+        self.finish_or_register(None)
+    }
+
+    /// Block until the download completes.
+    pub fn wait(mut self) -> Result<Vec<u8>, ReadbackError> {
+        if matches!(self.state, DownloadState::Mapping { .. }) {
+            self.device.poll(wgpu::PollType::wait_indefinitely())?;
+        }
+        self.try_finish()
+            .unwrap_or(Err(ReadbackError::MapChannelClosed))
+    }
+
+    /// [`Download::try_finish`], storing `waker` for the map callback if still in flight.
+    fn finish_or_register(
+        &mut self,
+        waker: Option<&Waker>,
+    ) -> Option<Result<Vec<u8>, ReadbackError>> {
+        match std::mem::replace(&mut self.state, DownloadState::Finished) {
+            DownloadState::Finished => None,
+            DownloadState::Failed(error) => Some(Err(error)),
+            DownloadState::Mapping {
+                staging,
+                status,
+                rows,
+            } => {
+                if let Err(error) = self.device.poll(wgpu::PollType::Poll) {
+                    return Some(Err(error.into()));
+                }
+                // Register under the lock so a callback racing this poll still wakes us.
+                let completed = match status.lock() {
+                    Ok(mut state) => {
+                        let result = state.result.take();
+                        if result.is_none()
+                            && let Some(waker) = waker
+                        {
+                            state.waker = Some(waker.clone());
+                        }
+                        result
+                    }
+                    Err(_) => return Some(Err(ReadbackError::MapChannelClosed)),
+                };
+                match completed {
+                    None => {
+                        self.state = DownloadState::Mapping {
+                            staging,
+                            status,
+                            rows,
+                        };
+                        None
+                    }
+                    Some(Err(error)) => Some(Err(error.into())),
+                    Some(Ok(())) => {
+                        let data = {
+                            let mapped = staging.slice(..).get_mapped_range();
+                            match rows {
+                                Some(rows) => strip_row_padding(&mapped, rows),
+                                None => mapped.to_vec(),
+                            }
+                        };
+                        staging.unmap();
+                        Some(Ok(data))
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, DownloadState::Finished)
+    }
+}
+
+impl Future for Download {
+    type Output = Result<Vec<u8>, ReadbackError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        assert!(!self.is_finished(), "Download polled after completion");
+        match self.finish_or_register(Some(cx.waker())) {
+            Some(result) => Poll::Ready(result),
+            None => Poll::Pending,
+        }
+    }
+}
+
+fn strip_row_padding(mapped: &[u8], rows: RowLayout) -> Vec<u8> {
+    mapped
+        .chunks(rows.padded_bytes_per_row as usize)
+        .take(rows.rows as usize)
+        .flat_map(|row| &row[..rows.bytes_per_row as usize])
+        .copied()
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BufferUsage, Renderer, ResourceRegistry};
+    use pollster::FutureExt;
+    use std::pin::pin;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::task::Wake;
+
+    fn renderer() -> Option<Renderer> {
+        match Renderer::new().block_on() {
+            Ok(renderer) => Some(renderer),
+            Err(err) => {
+                eprintln!("skip readback test: {err}");
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_padded_bytes_per_row() {
+        assert_eq!(padded_bytes_per_row(64, 4), 256);
+        assert_eq!(padded_bytes_per_row(3, 4), 256);
+        assert_eq!(padded_bytes_per_row(65, 4), 512);
+        assert_eq!(padded_bytes_per_row(0, 4), 0);
+    }
+
+    #[test]
+    fn test_strip_row_padding() {
+        let rows = RowLayout {
+            padded_bytes_per_row: 4,
+            bytes_per_row: 3,
+            rows: 2,
+        };
+        assert_eq!(
+            strip_row_padding(&[1, 2, 3, 0, 4, 5, 6, 0], rows),
+            vec![1, 2, 3, 4, 5, 6]
+        );
+    }
+
+    #[test]
+    fn test_read_buffer_async_round_trip() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let values = [1u32, 2, 3, 0xdead_beef];
+        let buffer = renderer
+            .create_gpu_buffer::<u32>()
+            .label("readback source")
+            .with_data(&values)
+            .usage(BufferUsage::Storage { read_only: true })
+            .add_usage(wgpu::BufferUsages::COPY_SRC)
+            .build(&mut registry)
+            .expect("buffer");
+
+        let bytes = renderer
+            .read_buffer_async(buffer.handle(), &registry)
+            .wait()
+            .expect("download");
+        let read: Vec<u32> = bytes
+            .chunks_exact(4)
+            .map(bytemuck::pod_read_unaligned)
+            .collect();
+        assert_eq!(read, values);
+    }
+
+    /// Waker that records whether it has been woken.
+    struct WakeFlag(AtomicBool);
+
+    impl Wake for WakeFlag {
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn test_download_is_woken_by_map_callback() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let buffer = renderer
+            .create_gpu_buffer::<u32>()
+            .label("readback source")
+            .with_data(&[7, 8])
+            .usage(BufferUsage::Storage { read_only: true })
+            .add_usage(wgpu::BufferUsages::COPY_SRC)
+            .build(&mut registry)
+            .expect("buffer");
+
+        let flag = Arc::new(WakeFlag(AtomicBool::new(false)));
+        let waker = Waker::from(Arc::clone(&flag));
+        let mut cx = Context::from_waker(&waker);
+        let mut download = pin!(renderer.read_buffer_async(buffer.handle(), &registry));
+        let result = match download.as_mut().poll(&mut cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => {
+                // No self-wake: the task stays idle until the map callback runs.
+                assert!(!flag.0.load(Ordering::SeqCst));
+                renderer
+                    .device()
+                    .poll(wgpu::PollType::wait_indefinitely())
+                    .expect("poll");
+                assert!(flag.0.load(Ordering::SeqCst));
+                match download.as_mut().poll(&mut cx) {
+                    Poll::Ready(result) => result,
+                    Poll::Pending => panic!("download pending after its wake"),
+                }
+            }
+        };
+        let bytes = result.expect("download");
+        assert_eq!(bytemuck::pod_read_unaligned::<[u32; 2]>(&bytes), [7, 8]);
+    }
+
+    #[test]
+    fn test_read_buffer_async_requires_copy_src() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let buffer = renderer
+            .create_gpu_buffer::<u32>()
+            .label("no copy src")
+            .with_data(&[0])
+            .usage(BufferUsage::Storage { read_only: true })
+            .build(&mut registry)
+            .expect("buffer");
+
+        let err = renderer
+            .read_buffer_async(buffer.handle(), &registry)
+            .block_on()
+            .expect_err("download should fail");
+        assert!(matches!(err, ReadbackError::MissingCopySrc));
+    }
+
+    #[test]
+    fn test_read_texture_async_strips_row_padding() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let (width, height) = (3u32, 2u32);
+        let texture = renderer
+            .create_texture()
+            .label("readback texture")
+            .size_2d(width, height)
+            .format(wgpu::TextureFormat::Rgba8Unorm)
+            .usage_flags(wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST)
+            .build(&mut registry)
+            .expect("texture");
+
+        let texels: Vec<u8> = (0..(width * height * 4) as u8).collect();
+        renderer.queue().write_texture(
+            wgpu::TexelCopyTextureInfo {
+                texture: registry.get(texture).expect("texture"),
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            &texels,
+            wgpu::TexelCopyBufferLayout {
+                offset: 0,
+                bytes_per_row: Some(width * 4),
+                rows_per_image: Some(height),
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+
+        let mut download = renderer.read_texture_async(texture, &registry);
+        let bytes = loop {
+            if let Some(result) = download.try_finish() {
+                break result.expect("download");
+            }
+            std::thread::yield_now();
+        };
+        assert_eq!(bytes, texels);
+        assert!(download.try_finish().is_none());
+    }
+}
@@ -17,6 +17,44 @@ pub enum DepthLoadOp {
     Clear(f32),
 }
 
+/// Depth buffer convention shared by projections, pipelines and depth clears.
+///
+/// `Reversed` maps the near plane to 1 and the far plane to 0, which spreads
+/// `Depth32Float` precision evenly over distance and avoids z-fighting in large scenes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DepthMode {
+    #[default]
+    Standard,
+    Reversed,
+}
+
+impl DepthMode {
+    /// Depth of the far plane; the value to clear depth attachments to.
+    pub fn far_depth(self) -> f32 {
+        match self {
+            Self::Standard => 1.0,
+            Self::Reversed => 0.0,
+        }
+    }
+
+    pub fn clear(self) -> DepthLoadOp {
+        DepthLoadOp::Clear(self.far_depth())
+    }
+
+    /// Translate a compare function written for standard depth into this mode.
+    pub fn compare(self, compare: wgpu::CompareFunction) -> wgpu::CompareFunction {
+        use wgpu::CompareFunction as C;
+        match (self, compare) {
+            (Self::Standard, compare) => compare,
+            (Self::Reversed, C::Less) => C::Greater,
+            (Self::Reversed, C::LessEqual) => C::GreaterEqual,
+            (Self::Reversed, C::Greater) => C::Less,
+            (Self::Reversed, C::GreaterEqual) => C::LessEqual,
+            (Self::Reversed, compare) => compare,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum RenderDraw {
     Direct {
@@ -659,6 +697,17 @@ impl RenderPassBuilder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_depth_mode_reverses_compare_and_clear() {
+        use wgpu::CompareFunction as C;
+        assert_eq!(DepthMode::Standard.compare(C::Less), C::Less);
+        assert_eq!(DepthMode::Reversed.compare(C::Less), C::Greater);
+        assert_eq!(DepthMode::Reversed.compare(C::LessEqual), C::GreaterEqual);
+        assert_eq!(DepthMode::Reversed.compare(C::Always), C::Always);
+        assert_eq!(DepthMode::Standard.far_depth(), 1.0);
+        assert_eq!(DepthMode::Reversed.far_depth(), 0.0);
+    }
+
     #[test]
     fn test_render_pass_builder_requires_pipeline() {
         let view = Handle::<wgpu::TextureView>::next();
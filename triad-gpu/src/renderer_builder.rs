@@ -0,0 +1,266 @@
+//! Adapter selection and device configuration for [`Renderer`].
+
+use crate::Renderer;
+use crate::error::RendererError;
+use wgpu::Instance;
+
+/// Builder for a [`Renderer`] with explicit adapter and device requirements.
+///
+/// The adapter wgpu picks for the requested power preference is used when it
+/// meets the required features and limits. Otherwise every adapter on the
+/// allowed backends is considered, hardware before software, and
+/// [`RendererError::NoSuitableAdapter`] lists why each one was rejected.
+#[derive(Debug, Clone)]
+pub struct RendererBuilder {
+    label: Option<String>,
+    power_preference: wgpu::PowerPreference,
+    backends: Option<wgpu::Backends>,
+    required_features: wgpu::Features,
+    required_limits: wgpu::Limits,
+    allow_software_adapter: bool,
+}
+
+impl Default for RendererBuilder {
+    fn default() -> Self {
+        Self {
+            label: None,
+            power_preference: wgpu::PowerPreference::default(),
+            backends: None,
+            required_features: wgpu::Features::empty(),
+            required_limits: wgpu::Limits::default(),
+            allow_software_adapter: true,
+        }
+    }
+}
+
+impl RendererBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Prefer a discrete (`HighPerformance`) or integrated (`LowPower`) GPU.
+    pub fn power_preference(mut self, preference: wgpu::PowerPreference) -> Self {
+        self.power_preference = preference;
+        self
+    }
+
+    /// Restrict adapters to these backends, e.g. `wgpu::Backends::VULKAN`.
+    /// Defaults to the `WGPU_BACKEND` environment variable, or all backends.
+    pub fn backends(mut self, backends: wgpu::Backends) -> Self {
+        self.backends = Some(backends);
+        self
+    }
+
+    pub fn required_features(mut self, features: wgpu::Features) -> Self {
+        self.required_features = features;
+        self
+    }
+
+    /// Limits the device must support, e.g. a larger `max_storage_buffer_binding_size`.
+    pub fn required_limits(mut self, limits: wgpu::Limits) -> Self {
+        self.required_limits = limits;
+        self
+    }
+
+    /// Whether a CPU/software adapter may be used when no hardware adapter qualifies.
+    pub fn allow_software_adapter(mut self, allow: bool) -> Self {
+        self.allow_software_adapter = allow;
+        self
+    }
+
+    // Tests hold the GPU lock across adapter/device creation on purpose.
+    #[cfg_attr(test, allow(clippy::await_holding_lock))]
+    pub async fn build(self) -> Result<Renderer, RendererError> {
+        #[cfg(test)]
+        let _gpu_test_guard = crate::test_util::gpu_test_lock();
+
+        let mut instance_desc = wgpu::InstanceDescriptor::from_env_or_default();
+        if let Some(backends) = self.backends {
+            instance_desc.backends = backends;
+        }
+        let instance = Instance::new(&instance_desc);
+
+        let preferred = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: self.power_preference,
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            })
+            .await
+            .ok()
+            .filter(|adapter| self.rejection_reason(adapter).is_none());
+
+        let adapter = match preferred {
+            Some(adapter) => adapter,
+            None => self.select_adapter(&instance, instance_desc.backends)?,
+        };
+        tracing::info!(adapter = ?adapter.get_info(), "selected GPU adapter");
+
+        let (device, queue) = adapter
+            .request_device(&wgpu::DeviceDescriptor {
+                label: Some(self.label.as_deref().unwrap_or("Renderer")),
+                required_features: self.required_features,
+                required_limits: self.required_limits.clone(),
+                ..Default::default()
+            })
+            .await?;
+
+        Ok(Renderer {
+            device,
+            queue,
+            instance,
+            adapter,
+        })
+    }
+
+    /// Pick the best-ranked adapter that meets the requirements.
+    fn select_adapter(
+        &self,
+        instance: &Instance,
+        backends: wgpu::Backends,
+    ) -> Result<wgpu::Adapter, RendererError> {
+        let mut candidates = Vec::new();
+        let mut available = Vec::new();
+        for adapter in instance.enumerate_adapters(backends) {
+            let info = adapter.get_info();
+            match self.rejection_reason(&adapter) {
+                Some(reason) => available.push(describe_adapter(&info, Some(&reason))),
+                None => {
+                    available.push(describe_adapter(&info, None));
+                    candidates.push((adapter_rank(&info, self.power_preference), adapter));
+                }
+            }
+        }
+        candidates.sort_by_key(|(rank, _)| *rank);
+        candidates
+            .into_iter()
+            .next()
+            .map(|(_, adapter)| adapter)
+            .ok_or(RendererError::NoSuitableAdapter { available })
+    }
+
+    fn rejection_reason(&self, adapter: &wgpu::Adapter) -> Option<String> {
+        let info = adapter.get_info();
+        if !self.allow_software_adapter && info.device_type == wgpu::DeviceType::Cpu {
+            return Some("software adapters are disallowed".to_string());
+        }
+        let missing = self.required_features - adapter.features();
+        if !missing.is_empty() {
+            return Some(format!("missing features {missing:?}"));
+        }
+        let mut failed = Vec::new();
+        self.required_limits
+            .check_limits_with_fail_fn(&adapter.limits(), false, |name, _, _| failed.push(name));
+        if !failed.is_empty() {
+            return Some(format!("insufficient limits {}", failed.join(", ")));
+        }
+        None
+    }
+}
+
+/// Lower is better: device types matching the power preference first, software last.
+fn adapter_rank(info: &wgpu::AdapterInfo, preference: wgpu::PowerPreference) -> u8 {
+    use wgpu::DeviceType;
+    match (preference, info.device_type) {
+        (wgpu::PowerPreference::HighPerformance, DeviceType::DiscreteGpu)
+        | (wgpu::PowerPreference::LowPower, DeviceType::IntegratedGpu) => 0,
+        (_, DeviceType::DiscreteGpu | DeviceType::IntegratedGpu) => 1,
+        (_, DeviceType::VirtualGpu | DeviceType::Other) => 2,
+        (_, DeviceType::Cpu) => 3,
+    }
+}
+
+fn describe_adapter(info: &wgpu::AdapterInfo, rejection: Option<&str>) -> String {
+    let summary = format!("{} ({:?}, {:?})", info.name, info.backend, info.device_type);
+    match rejection {
+        Some(reason) => format!("{summary}: {reason}"),
+        None => summary,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pollster::FutureExt;
+
+    fn info(name: &str, device_type: wgpu::DeviceType) -> wgpu::AdapterInfo {
+        wgpu::AdapterInfo {
+            name: name.to_string(),
+            vendor: 0,
+            device: 0,
+            device_type,
+            driver: String::new(),
+            driver_info: String::new(),
+            backend: wgpu::Backend::Vulkan,
+        }
+    }
+
+    #[test]
+    fn test_adapter_rank_follows_power_preference() {
+        let discrete = info("discrete", wgpu::DeviceType::DiscreteGpu);
+        let integrated = info("integrated", wgpu::DeviceType::IntegratedGpu);
+        let cpu = info("cpu", wgpu::DeviceType::Cpu);
+
+        let high = wgpu::PowerPreference::HighPerformance;
+        assert!(adapter_rank(&discrete, high) < adapter_rank(&integrated, high));
+        let low = wgpu::PowerPreference::LowPower;
+        assert!(adapter_rank(&integrated, low) < adapter_rank(&discrete, low));
+        assert!(adapter_rank(&integrated, low) < adapter_rank(&cpu, low));
+        assert!(adapter_rank(&discrete, high) < adapter_rank(&cpu, high));
+    }
+
+    #[test]
+    fn test_describe_adapter_includes_rejection() {
+        let cpu = info("llvmpipe", wgpu::DeviceType::Cpu);
+        assert_eq!(describe_adapter(&cpu, None), "llvmpipe (Vulkan, Cpu)");
+        assert_eq!(
+            describe_adapter(&cpu, Some("missing features")),
+            "llvmpipe (Vulkan, Cpu): missing features"
+        );
+    }
+
+    #[test]
+    fn test_builder_reports_adapters_for_unsatisfiable_limits() {
+        let result = RendererBuilder::new()
+            .required_limits(wgpu::Limits {
+                max_bind_groups: u32::MAX,
+                ..wgpu::Limits::default()
+            })
+            .build()
+            .block_on();
+        match result {
+            Err(RendererError::NoSuitableAdapter { available }) => {
+                assert!(
+                    available
+                        .iter()
+                        .all(|entry| entry.contains("max_bind_groups"))
+                );
+            }
+            Err(err) => panic!("unexpected error: {err}"),
+            Ok(_) => panic!("no adapter should support u32::MAX bind groups"),
+        }
+    }
+
+    #[test]
+    fn test_builder_with_defaults() {
+        let renderer = match RendererBuilder::new()
+            .label("builder test")
+            .power_preference(wgpu::PowerPreference::LowPower)
+            .build()
+            .block_on()
+        {
+            Ok(renderer) => renderer,
+            Err(err) => {
+                eprintln!("skipping renderer builder test: {err}");
+                return;
+            }
+        };
+        assert!(renderer.device().limits().max_bind_groups >= 4);
+        assert_eq!(renderer.adapter().get_info(), renderer.adapter_info());
+    }
+}
@@ -0,0 +1,903 @@
+//! Exclusive prefix sum (scan) and reductions over `u32` storage buffers.
+//!
+//! Both run as a hierarchy of 256-wide workgroup kernels: level 0 processes the input
+//! one tile per workgroup and writes one value per tile, level 1 processes those tile
+//! values, and so on until a single workgroup remains. The scan then walks back down,
+//! adding each tile's scanned offset to its elements. [`scan_levels`] gives the
+//! per-level element and workgroup counts.
+//!
+//! Record either one into an encoder with `encode`, or add it to a frame graph with `pass`.
+
+use crate::compute::workgroup_count;
+use crate::error::{BufferError, PipelineError, ShaderError};
+use crate::frame_graph::pass::{Pass, PassBuilder, PassContext};
+use crate::frame_graph::{Handle, ResourceType};
+use crate::resource_registry::ResourceRegistry;
+use crate::{BufferUsage, ComputePipelineBuilder, Renderer, ShaderModuleBuilder};
+use thiserror::Error;
+
+/// Threads per workgroup (and elements per tile) for every scan and reduction kernel.
+pub const SCAN_WORKGROUP_SIZE: u32 = 256;
+
+/// One level of a multi-level scan or reduction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScanLevel {
+    /// Elements processed at this level.
+    pub len: u32,
+    /// Workgroups dispatched, which is also the number of tile values written.
+    pub workgroups: u32,
+}
+
+/// Levels needed to scan or reduce `len` elements. The last level always has a
+/// single workgroup; `len == 0` yields no levels.
+pub fn scan_levels(len: u32) -> Vec<ScanLevel> {
+    let mut levels = Vec::new();
+    let mut len = len;
+    while len > 0 {
+        let workgroups = workgroup_count(len, SCAN_WORKGROUP_SIZE);
+        levels.push(ScanLevel { len, workgroups });
+        if workgroups == 1 {
+            break;
+        }
+        len = workgroups;
+    }
+    levels
+}
+
+#[derive(Debug, Error)]
+pub enum ScanError {
+    #[error("cannot scan or reduce an empty buffer")]
+    Empty,
+    #[error("{len} elements exceed the single-dispatch maximum of {max}")]
+    TooLarge { len: u32, max: u32 },
+    #[error("{0} missing from registry")]
+    MissingResource(&'static str),
+    #[error(transparent)]
+    Buffer(#[from] BufferError),
+    #[error(transparent)]
+    Shader(#[from] ShaderError),
+    #[error(transparent)]
+    Pipeline(#[from] PipelineError),
+}
+
+pub type ScanResult<T> = Result<T, ScanError>;
+
+/// Matches WGSL `struct Params { len: u32 }` padded to 16 bytes.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct LevelParams {
+    len: u32,
+    _pad: [u32; 3],
+}
+
+const WGSL_PARAMS: &str = r#"
+struct Params {
+    len: u32,
+    _pad0: u32,
+    _pad1: u32,
+    _pad2: u32,
+}
+
+@group(0) @binding(0) var<uniform> params: Params;
+"#;
+
+/// Exclusive scan of one tile in place; the tile total goes to `tile_sums[workgroup]`.
+const WGSL_SCAN_TILES: &str = r#"
+@group(0) @binding(1) var<storage, read_write> data: array<u32>;
+@group(0) @binding(2) var<storage, read_write> tile_sums: array<u32>;
+
+var<workgroup> tile: array<u32, 256>;
+
+@compute @workgroup_size(256)
+fn cs_main(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_id) lid: vec3<u32>,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    let i = gid.x;
+    let l = lid.x;
+    var value = 0u;
+    if (i < params.len) {
+        value = data[i];
+    }
+    tile[l] = value;
+    workgroupBarrier();
+
+    // Hillis–Steele inclusive scan in shared memory.
+    for (var stride = 1u; stride < 256u; stride = stride << 1u) {
+        var addend = 0u;
+        if (l >= stride) {
+            addend = tile[l - stride];
+        }
+        workgroupBarrier();
+        tile[l] = tile[l] + addend;
+        workgroupBarrier();
+    }
+
+    if (i < params.len) {
+        data[i] = tile[l] - value;
+    }
+    if (l == 255u) {
+        tile_sums[wid.x] = tile[l];
+    }
+}
+"#;
+
+/// Add each tile's scanned offset to its elements.
+const WGSL_ADD_TILE_OFFSETS: &str = r#"
+@group(0) @binding(1) var<storage, read_write> data: array<u32>;
+@group(0) @binding(2) var<storage, read_write> tile_sums: array<u32>;
+
+@compute @workgroup_size(256)
+fn cs_main(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    let i = gid.x;
+    if (i >= params.len) { return; }
+    data[i] = data[i] + tile_sums[wid.x];
+}
+"#;
+
+/// Tree reduction of one tile; expects `IDENTITY` and `combine` from [`ReduceOp::wgsl`].
+const WGSL_REDUCE_TILES: &str = r#"
+@group(0) @binding(1) var<storage, read> input: array<u32>;
+@group(0) @binding(2) var<storage, read_write> output: array<u32>;
+
+var<workgroup> tile: array<u32, 256>;
+
+@compute @workgroup_size(256)
+fn cs_main(
+    @builtin(global_invocation_id) gid: vec3<u32>,
+    @builtin(local_invocation_id) lid: vec3<u32>,
+    @builtin(workgroup_id) wid: vec3<u32>,
+) {
+    let l = lid.x;
+    var value = IDENTITY;
+    if (gid.x < params.len) {
+        value = input[gid.x];
+    }
+    tile[l] = value;
+    workgroupBarrier();
+
+    for (var stride = 128u; stride > 0u; stride = stride >> 1u) {
+        if (l < stride) {
+            tile[l] = combine(tile[l], tile[l + stride]);
+        }
+        workgroupBarrier();
+    }
+
+    if (l == 0u) {
+        output[wid.x] = tile[0];
+    }
+}
+"#;
+
+/// Combining operation for [`GpuReduce`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReduceOp {
+    /// Wrapping sum.
+    Sum,
+    Min,
+    Max,
+}
+
+impl ReduceOp {
+    fn wgsl(self) -> &'static str {
+        match self {
+            ReduceOp::Sum => {
+                "const IDENTITY: u32 = 0u;\nfn combine(a: u32, b: u32) -> u32 { return a + b; }\n"
+            }
+            ReduceOp::Min => {
+                "const IDENTITY: u32 = 0xffffffffu;\nfn combine(a: u32, b: u32) -> u32 { return min(a, b); }\n"
+            }
+            ReduceOp::Max => {
+                "const IDENTITY: u32 = 0u;\nfn combine(a: u32, b: u32) -> u32 { return max(a, b); }\n"
+            }
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            ReduceOp::Sum => "sum",
+            ReduceOp::Min => "min",
+            ReduceOp::Max => "max",
+        }
+    }
+}
+
+/// Levels for `len` elements, rejecting sizes a single level-0 dispatch cannot cover.
+fn checked_levels(renderer: &Renderer, len: u32) -> ScanResult<Vec<ScanLevel>> {
+    if len == 0 {
+        return Err(ScanError::Empty);
+    }
+    let max = renderer
+        .device()
+        .limits()
+        .max_compute_workgroups_per_dimension
+        .saturating_mul(SCAN_WORKGROUP_SIZE);
+    if len > max {
+        return Err(ScanError::TooLarge { len, max });
+    }
+    Ok(scan_levels(len))
+}
+
+fn buffer_entry(binding: u32, ty: wgpu::BufferBindingType) -> wgpu::BindGroupLayoutEntry {
+    wgpu::BindGroupLayoutEntry {
+        binding,
+        visibility: wgpu::ShaderStages::COMPUTE,
+        ty: wgpu::BindingType::Buffer {
+            ty,
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        count: None,
+    }
+}
+
+/// `params` uniform at binding 0, `input` at 1 and `output` at 2.
+fn level_layout(
+    device: &wgpu::Device,
+    label: &str,
+    input_read_only: bool,
+) -> wgpu::BindGroupLayout {
+    device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+        label: Some(label),
+        entries: &[
+            buffer_entry(0, wgpu::BufferBindingType::Uniform),
+            buffer_entry(
+                1,
+                wgpu::BufferBindingType::Storage {
+                    read_only: input_read_only,
+                },
+            ),
+            buffer_entry(2, wgpu::BufferBindingType::Storage { read_only: false }),
+        ],
+    })
+}
+
+/// Uniform, input and output buffers for each level, bound with `layout`.
+fn level_bind_groups(
+    renderer: &Renderer,
+    registry: &mut ResourceRegistry,
+    label: &str,
+    layout: &wgpu::BindGroupLayout,
+    levels: &[ScanLevel],
+    input: Handle<wgpu::Buffer>,
+    outputs: &[Handle<wgpu::Buffer>],
+) -> ScanResult<Vec<Handle<wgpu::BindGroup>>> {
+    let mut bind_groups = Vec::with_capacity(levels.len());
+    for (index, level) in levels.iter().enumerate() {
+        let params = renderer
+            .create_gpu_buffer::<LevelParams>()
+            .label(format!("{label} params {index}"))
+            .with_data(&[LevelParams {
+                len: level.len,
+                _pad: [0; 3],
+            }])
+            .usage(BufferUsage::Uniform)
+            .build(registry)?;
+        let level_input = if index == 0 {
+            input
+        } else {
+            outputs[index - 1]
+        };
+        let get = |handle| registry.get(handle).ok_or(BufferError::NotFound);
+        let bind_group = renderer
+            .device()
+            .create_bind_group(&wgpu::BindGroupDescriptor {
+                label: Some(&format!("{label} level {index}")),
+                layout,
+                entries: &[
+                    wgpu::BindGroupEntry {
+                        binding: 0,
+                        resource: get(params.handle())?.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 1,
+                        resource: get(level_input)?.as_entire_binding(),
+                    },
+                    wgpu::BindGroupEntry {
+                        binding: 2,
+                        resource: get(outputs[index])?.as_entire_binding(),
+                    },
+                ],
+            });
+        bind_groups.push(registry.insert(bind_group));
+    }
+    Ok(bind_groups)
+}
+
+/// Per-level tile outputs; the last holds a single value.
+fn tile_buffers(
+    renderer: &Renderer,
+    registry: &mut ResourceRegistry,
+    label: &str,
+    levels: &[ScanLevel],
+) -> ScanResult<Vec<Handle<wgpu::Buffer>>> {
+    levels
+        .iter()
+        .enumerate()
+        .map(|(index, level)| {
+            Ok(renderer
+                .create_gpu_buffer::<u32>()
+                .label(format!("{label} tiles {index}"))
+                .capacity(level.workgroups as usize)
+                .usage(BufferUsage::Storage { read_only: false })
+                .add_usage(wgpu::BufferUsages::COPY_SRC)
+                .build(registry)?
+                .handle())
+        })
+        .collect()
+}
+
+fn compute_pipeline(
+    renderer: &Renderer,
+    registry: &mut ResourceRegistry,
+    label: &str,
+    layout: &wgpu::BindGroupLayout,
+    source: String,
+) -> ScanResult<Handle<wgpu::ComputePipeline>> {
+    let device = renderer.device();
+    let shader = ShaderModuleBuilder::new(device)
+        .label(label)
+        .with_wgsl_source(source)
+        .build(registry)?;
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: Some(label),
+        bind_group_layouts: &[layout],
+        push_constant_ranges: &[],
+    });
+    Ok(ComputePipelineBuilder::new(device)
+        .with_label(label)
+        .with_compute_shader(shader)
+        .with_layout(pipeline_layout)
+        .build(registry)?)
+}
+
+fn lookup<'a, T: ResourceType>(
+    registry: &'a ResourceRegistry,
+    handle: Handle<T>,
+    what: &'static str,
+) -> ScanResult<&'a T> {
+    registry.get(handle).ok_or(ScanError::MissingResource(what))
+}
+
+fn lookup_bind_groups<'a>(
+    registry: &'a ResourceRegistry,
+    handles: &[Handle<wgpu::BindGroup>],
+) -> ScanResult<Vec<&'a wgpu::BindGroup>> {
+    handles
+        .iter()
+        .map(|handle| lookup(registry, *handle, "scan bind group"))
+        .collect()
+}
+
+fn encode_level(
+    encoder: &mut wgpu::CommandEncoder,
+    label: &str,
+    pipeline: &wgpu::ComputePipeline,
+    bind_group: &wgpu::BindGroup,
+    workgroups: u32,
+) {
+    let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+        label: Some(label),
+        timestamp_writes: None,
+    });
+    pass.set_pipeline(pipeline);
+    pass.set_bind_group(0, bind_group, &[]);
+    pass.dispatch_workgroups(workgroups, 1, 1);
+}
+
+/// In-place exclusive prefix sum over the first `len` `u32`s of a storage buffer.
+///
+/// The buffer must have `STORAGE` usage. After [`GpuScan::encode`], element `i`
+/// holds the wrapping sum of elements `0..i`, and [`GpuScan::total`] holds the
+/// sum of all `len` elements at index 0.
+#[derive(Clone)]
+pub struct GpuScan {
+    data: Handle<wgpu::Buffer>,
+    levels: Vec<ScanLevel>,
+    tile_sums: Vec<Handle<wgpu::Buffer>>,
+    bind_groups: Vec<Handle<wgpu::BindGroup>>,
+    pipeline_scan_tiles: Handle<wgpu::ComputePipeline>,
+    pipeline_add_offsets: Handle<wgpu::ComputePipeline>,
+}
+
+impl GpuScan {
+    pub fn new(
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        data: Handle<wgpu::Buffer>,
+        len: u32,
+    ) -> ScanResult<Self> {
+        let levels = checked_levels(renderer, len)?;
+        let layout = level_layout(renderer.device(), "scan layout", false);
+        let tile_sums = tile_buffers(renderer, registry, "scan", &levels)?;
+        let bind_groups = level_bind_groups(
+            renderer, registry, "scan", &layout, &levels, data, &tile_sums,
+        )?;
+
+        let pipeline_scan_tiles = compute_pipeline(
+            renderer,
+            registry,
+            "scan tiles",
+            &layout,
+            format!("{WGSL_PARAMS}{WGSL_SCAN_TILES}"),
+        )?;
+        let pipeline_add_offsets = compute_pipeline(
+            renderer,
+            registry,
+            "scan add tile offsets",
+            &layout,
+            format!("{WGSL_PARAMS}{WGSL_ADD_TILE_OFFSETS}"),
+        )?;
+
+        Ok(Self {
+            data,
+            levels,
+            tile_sums,
+            bind_groups,
+            pipeline_scan_tiles,
+            pipeline_add_offsets,
+        })
+    }
+
+    /// The scanned buffer.
+    pub fn data(&self) -> Handle<wgpu::Buffer> {
+        self.data
+    }
+
+    pub fn levels(&self) -> &[ScanLevel] {
+        &self.levels
+    }
+
+    /// Single-element buffer receiving the sum of all elements.
+    pub fn total(&self) -> Handle<wgpu::Buffer> {
+        *self.tile_sums.last().expect("scan has at least one level")
+    }
+
+    /// Encode the scan: tiles up through every level, then tile offsets back down.
+    pub fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        registry: &ResourceRegistry,
+    ) -> ScanResult<()> {
+        let scan_tiles = lookup(registry, self.pipeline_scan_tiles, "scan tiles pipeline")?;
+        let add_offsets = lookup(
+            registry,
+            self.pipeline_add_offsets,
+            "scan add offsets pipeline",
+        )?;
+        let bind_groups = lookup_bind_groups(registry, &self.bind_groups)?;
+
+        for (level, bind_group) in self.levels.iter().zip(&bind_groups) {
+            encode_level(
+                encoder,
+                "scan tiles",
+                scan_tiles,
+                bind_group,
+                level.workgroups,
+            );
+        }
+        // The top level is a single tile, so its offsets are already final.
+        let lower_levels = self.levels.len() - 1;
+        for (level, bind_group) in self.levels[..lower_levels].iter().zip(&bind_groups).rev() {
+            encode_level(
+                encoder,
+                "scan add tile offsets",
+                add_offsets,
+                bind_group,
+                level.workgroups,
+            );
+        }
+        Ok(())
+    }
+
+    /// Frame-graph pass running [`GpuScan::encode`]. Reads and writes [`GpuScan::data`]
+    /// and writes [`GpuScan::total`].
+    ///
+    /// Fails here if a resource is missing from `registry`, so the pass itself cannot.
+    pub fn pass(
+        &self,
+        name: impl Into<String>,
+        registry: &ResourceRegistry,
+    ) -> ScanResult<PassBuilder> {
+        lookup(registry, self.pipeline_scan_tiles, "scan tiles pipeline")?;
+        lookup(
+            registry,
+            self.pipeline_add_offsets,
+            "scan add offsets pipeline",
+        )?;
+        lookup_bind_groups(registry, &self.bind_groups)?;
+        let name = name.into();
+        let mut builder = PassBuilder::new(name.clone());
+        builder.read_write(self.data);
+        for tile_sums in &self.tile_sums {
+            builder.write(*tile_sums);
+        }
+        Ok(builder.with_pass(Box::new(ScanPass {
+            name,
+            kernel: ScanKernel::Scan(self.clone()),
+        })))
+    }
+}
+
+/// Reduction of the first `len` `u32`s of a storage buffer to a single value.
+///
+/// The input buffer is only read. After [`GpuReduce::encode`], [`GpuReduce::result`]
+/// holds the reduced value at index 0.
+#[derive(Clone)]
+pub struct GpuReduce {
+    op: ReduceOp,
+    input: Handle<wgpu::Buffer>,
+    levels: Vec<ScanLevel>,
+    partials: Vec<Handle<wgpu::Buffer>>,
+    bind_groups: Vec<Handle<wgpu::BindGroup>>,
+    pipeline: Handle<wgpu::ComputePipeline>,
+}
+
+impl GpuReduce {
+    pub fn new(
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        input: Handle<wgpu::Buffer>,
+        len: u32,
+        op: ReduceOp,
+    ) -> ScanResult<Self> {
+        let levels = checked_levels(renderer, len)?;
+        let label = format!("reduce {}", op.label());
+        let layout = level_layout(renderer.device(), &label, true);
+        let partials = tile_buffers(renderer, registry, &label, &levels)?;
+        let bind_groups = level_bind_groups(
+            renderer, registry, &label, &layout, &levels, input, &partials,
+        )?;
+        let pipeline = compute_pipeline(
+            renderer,
+            registry,
+            &label,
+            &layout,
+            format!("{WGSL_PARAMS}{}{WGSL_REDUCE_TILES}", op.wgsl()),
+        )?;
+
+        Ok(Self {
+            op,
+            input,
+            levels,
+            partials,
+            bind_groups,
+            pipeline,
+        })
+    }
+
+    pub fn op(&self) -> ReduceOp {
+        self.op
+    }
+
+    pub fn levels(&self) -> &[ScanLevel] {
+        &self.levels
+    }
+
+    /// Single-element buffer receiving the reduced value.
+    pub fn result(&self) -> Handle<wgpu::Buffer> {
+        *self
+            .partials
+            .last()
+            .expect("reduction has at least one level")
+    }
+
+    pub fn encode(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        registry: &ResourceRegistry,
+    ) -> ScanResult<()> {
+        let pipeline = lookup(registry, self.pipeline, "reduce pipeline")?;
+        let bind_groups = lookup_bind_groups(registry, &self.bind_groups)?;
+        for (level, bind_group) in self.levels.iter().zip(bind_groups) {
+            encode_level(
+                encoder,
+                "reduce tiles",
+                pipeline,
+                bind_group,
+                level.workgroups,
+            );
+        }
+        Ok(())
+    }
+
+    /// Frame-graph pass running [`GpuReduce::encode`]. Reads the input buffer and
+    /// writes [`GpuReduce::result`].
+    ///
+    /// Fails here if a resource is missing from `registry`, so the pass itself cannot.
+    pub fn pass(
+        &self,
+        name: impl Into<String>,
+        registry: &ResourceRegistry,
+    ) -> ScanResult<PassBuilder> {
+        lookup(registry, self.pipeline, "reduce pipeline")?;
+        lookup_bind_groups(registry, &self.bind_groups)?;
+        let name = name.into();
+        let mut builder = PassBuilder::new(name.clone());
+        builder.read(self.input);
+        for partials in &self.partials {
+            builder.write(*partials);
+        }
+        Ok(builder.with_pass(Box::new(ScanPass {
+            name,
+            kernel: ScanKernel::Reduce(self.clone()),
+        })))
+    }
+}
+
+enum ScanKernel {
+    Scan(GpuScan),
+    Reduce(GpuReduce),
+}
+
+/// Records a scan or reduction into its own encoder.
+struct ScanPass {
+    name: String,
+    kernel: ScanKernel,
+}
+
+impl Pass for ScanPass {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn execute(&self, ctx: &PassContext) -> wgpu::CommandBuffer {
+        let mut encoder = ctx.create_command_encoder(Some(&self.name));
+        let encoded = match &self.kernel {
+            ScanKernel::Scan(scan) => scan.encode(&mut encoder, ctx.resources),
+            ScanKernel::Reduce(reduce) => reduce.encode(&mut encoder, ctx.resources),
+        };
+        encoded.expect("scan resources are checked when the pass is built");
+        encoder.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pollster::FutureExt;
+
+    fn renderer() -> Option<Renderer> {
+        match Renderer::new().block_on() {
+            Ok(renderer) => Some(renderer),
+            Err(err) => {
+                eprintln!("skip scan test: {err}");
+                None
+            }
+        }
+    }
+
+    fn input_buffer(
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        values: &[u32],
+    ) -> Handle<wgpu::Buffer> {
+        renderer
+            .create_gpu_buffer::<u32>()
+            .label("scan test input")
+            .with_data(values)
+            .usage(BufferUsage::StorageWritable)
+            .add_usage(wgpu::BufferUsages::COPY_SRC)
+            .build(registry)
+            .expect("input buffer")
+            .handle()
+    }
+
+    /// Run `encode`, then copy `len` values out of `source` and read them back.
+    fn run_and_read(
+        renderer: &Renderer,
+        registry: &mut ResourceRegistry,
+        encode: impl FnOnce(&mut wgpu::CommandEncoder, &ResourceRegistry),
+        source: Handle<wgpu::Buffer>,
+        len: usize,
+    ) -> Vec<u32> {
+        let readback = renderer
+            .create_gpu_buffer::<u32>()
+            .label("scan test readback")
+            .capacity(len)
+            .usage(BufferUsage::Readback)
+            .build(registry)
+            .expect("readback buffer");
+
+        let mut encoder =
+            renderer
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("scan test"),
+                });
+        encode(&mut encoder, registry);
+        encoder.copy_buffer_to_buffer(
+            registry.get(source).expect("source"),
+            0,
+            registry.get(readback.handle()).expect("readback"),
+            0,
+            (len * 4) as u64,
+        );
+        renderer.queue().submit([encoder.finish()]);
+        renderer
+            .read_buffer::<u32>(readback.handle(), registry)
+            .expect("read back")
+    }
+
+    fn cpu_exclusive_scan(values: &[u32]) -> Vec<u32> {
+        values
+            .iter()
+            .scan(0u32, |sum, value| {
+                let prefix = *sum;
+                *sum = sum.wrapping_add(*value);
+                Some(prefix)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_scan_levels() {
+        assert!(scan_levels(0).is_empty());
+        assert_eq!(
+            scan_levels(256),
+            vec![ScanLevel {
+                len: 256,
+                workgroups: 1
+            }]
+        );
+        assert_eq!(
+            scan_levels(70_000),
+            vec![
+                ScanLevel {
+                    len: 70_000,
+                    workgroups: 274
+                },
+                ScanLevel {
+                    len: 274,
+                    workgroups: 2
+                },
+                ScanLevel {
+                    len: 2,
+                    workgroups: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_rejects_empty_input() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let data = input_buffer(&renderer, &mut registry, &[0]);
+        let err = GpuScan::new(&renderer, &mut registry, data, 0)
+            .err()
+            .expect("empty scan should fail");
+        assert!(matches!(err, ScanError::Empty));
+    }
+
+    #[test]
+    fn test_workgroup_scan_matches_cpu() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let values: Vec<u32> = (0..200).map(|i| i % 5 + 1).collect();
+        let data = input_buffer(&renderer, &mut registry, &values);
+        let scan = GpuScan::new(&renderer, &mut registry, data, values.len() as u32).expect("scan");
+        assert_eq!(scan.levels().len(), 1);
+
+        let scanned = run_and_read(
+            &renderer,
+            &mut registry,
+            |encoder, registry| scan.encode(encoder, registry).expect("encode"),
+            data,
+            values.len(),
+        );
+        assert_eq!(scanned, cpu_exclusive_scan(&values));
+
+        let total = run_and_read(&renderer, &mut registry, |_, _| {}, scan.total(), 1);
+        assert_eq!(total[0], values.iter().sum::<u32>());
+    }
+
+    #[test]
+    fn test_device_scan_matches_cpu() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let values: Vec<u32> = (0..70_000u32).map(|i| (i * 7919) % 13).collect();
+        // Trailing elements past `len` must be left untouched.
+        let mut padded = values.clone();
+        padded.extend([99, 99]);
+        let data = input_buffer(&renderer, &mut registry, &padded);
+        let scan = GpuScan::new(&renderer, &mut registry, data, values.len() as u32).expect("scan");
+        assert_eq!(scan.levels().len(), 3);
+
+        let scanned = run_and_read(
+            &renderer,
+            &mut registry,
+            |encoder, registry| scan.encode(encoder, registry).expect("encode"),
+            data,
+            padded.len(),
+        );
+        assert_eq!(scanned[..values.len()], cpu_exclusive_scan(&values));
+        assert_eq!(scanned[values.len()..], [99, 99]);
+
+        let total = run_and_read(&renderer, &mut registry, |_, _| {}, scan.total(), 1);
+        assert_eq!(total[0], values.iter().sum::<u32>());
+    }
+
+    #[test]
+    fn test_reductions_match_cpu() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let values: Vec<u32> = (0..1000u32)
+            .map(|i| i.wrapping_mul(2_654_435_761) >> 20)
+            .collect();
+        let input = input_buffer(&renderer, &mut registry, &values);
+
+        let expected = [
+            (
+                ReduceOp::Sum,
+                values.iter().fold(0u32, |a, b| a.wrapping_add(*b)),
+            ),
+            (ReduceOp::Min, *values.iter().min().unwrap()),
+            (ReduceOp::Max, *values.iter().max().unwrap()),
+        ];
+        for (op, expected) in expected {
+            let reduce = GpuReduce::new(&renderer, &mut registry, input, values.len() as u32, op)
+                .expect("reduce");
+            let result = run_and_read(
+                &renderer,
+                &mut registry,
+                |encoder, registry| reduce.encode(encoder, registry).expect("encode"),
+                reduce.result(),
+                1,
+            );
+            assert_eq!(result[0], expected, "{op:?}");
+        }
+    }
+
+    #[test]
+    fn test_scan_and_reduce_passes_in_frame_graph() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let values: Vec<u32> = (0..1000u32).map(|i| i % 7 + 1).collect();
+        let len = values.len() as u32;
+        let data = input_buffer(&renderer, &mut registry, &values);
+        let scan = GpuScan::new(&renderer, &mut registry, data, len).expect("scan");
+        let reduce =
+            GpuReduce::new(&renderer, &mut registry, data, len, ReduceOp::Max).expect("reduce");
+
+        // The reduction reads the scanned data, so the graph orders it after the scan.
+        let mut graph = crate::FrameGraph::new();
+        graph.add_pass(scan.pass("scan", &registry).expect("scan pass"));
+        graph.add_pass(reduce.pass("max", &registry).expect("reduce pass"));
+        let mut executable = graph.build().expect("graph");
+        assert_eq!(executable.execution_order(), [0, 1]);
+        executable.execute(renderer.device(), renderer.queue(), &registry);
+
+        let expected = cpu_exclusive_scan(&values);
+        let scanned = run_and_read(&renderer, &mut registry, |_, _| {}, data, values.len());
+        assert_eq!(scanned, expected);
+        let max = run_and_read(&renderer, &mut registry, |_, _| {}, reduce.result(), 1);
+        assert_eq!(max[0], *expected.last().unwrap());
+    }
+
+    #[test]
+    fn test_pass_requires_registered_resources() {
+        let Some(renderer) = renderer() else { return };
+        let mut registry = ResourceRegistry::default();
+        let data = input_buffer(&renderer, &mut registry, &[1, 2, 3]);
+        let scan = GpuScan::new(&renderer, &mut registry, data, 3).expect("scan");
+
+        let other = ResourceRegistry::default();
+        let err = scan.pass("scan", &other).err().expect("missing pipeline");
+        assert!(matches!(err, ScanError::MissingResource(_)));
+        let mut encoder =
+            renderer
+                .device()
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("scan test"),
+                });
+        let err = scan
+            .encode(&mut encoder, &other)
+            .expect_err("missing pipeline");
+        assert!(matches!(err, ScanError::MissingResource(_)));
+    }
+}
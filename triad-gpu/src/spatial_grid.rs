@@ -2,12 +2,12 @@
 //! init per-cell write heads → scatter entity indices into cell-major order.
 //!
 //! Intended for broadphase / binning (neighbor queries, culling prep). The exclusive scan
-//! uses a parallel Hillis–Steele inclusive pass (ping-pong buffers, one dispatch per
-//! `ceil(log2(cells))` stride) so cell count scales across the GPU.
+//! over cell counts is the shared [`GpuScan`], run in place on `cell_offsets`.
 
 use crate::error::{BindGroupError, BufferError, PipelineError, ShaderError};
 use crate::frame_graph::Handle;
 use crate::resource_registry::ResourceRegistry;
+use crate::scan::{GpuScan, ScanError};
 use crate::{BufferUsage, ComputePipelineBuilder, Renderer, ShaderModuleBuilder};
 use thiserror::Error;
 
@@ -77,6 +77,10 @@ pub enum SpatialGridError {
     Pipeline(#[from] PipelineError),
     #[error(transparent)]
     BindGroup(#[from] BindGroupError),
+    #[error(transparent)]
+    Scan(#[from] ScanError),
+    #[error("{0} missing from registry")]
+    MissingResource(&'static str),
 }
 
 pub type SpatialGridResult<T> = Result<T, SpatialGridError>;
@@ -142,58 +146,11 @@ fn cs_main(@builtin(global_invocation_id) gid: vec3<u32>) {
 }
 "#;
 
+/// Also seeds `cell_offsets` with the counts plus a trailing zero, so scanning all
+/// `n + 1` slots leaves the total in the sentinel `cell_offsets[n]`.
 const WGSL_LINEARIZE: &str = r#"
 @group(0) @binding(1) var<storage, read_write> cell_atomics: array<atomic<u32>>;
 @group(0) @binding(2) var<storage, read_write> counts_linear: array<u32>;
-
-@compute @workgroup_size(256)
-fn cs_main(@builtin(global_invocation_id) gid: vec3<u32>) {
-    let i = gid.x;
-    let n = total_cells();
-    if (i >= n) { return; }
-    counts_linear[i] = atomicLoad(&cell_atomics[i]);
-}
-"#;
-
-/// Copy per-cell counts into the inclusive-scan working buffer (ping-pong chain start).
-const WGSL_SCAN_COPY: &str = r#"
-@group(0) @binding(1) var<storage, read> counts_for_scan: array<u32>;
-@group(0) @binding(2) var<storage, read_write> scan_work: array<u32>;
-
-@compute @workgroup_size(256)
-fn cs_main(@builtin(global_invocation_id) gid: vec3<u32>) {
-    let i = gid.x;
-    let n = total_cells();
-    if (i >= n) { return; }
-    scan_work[i] = counts_for_scan[i];
-}
-"#;
-
-fn wgsl_scan_inclusive_step(stride: u32) -> String {
-    format!(
-        r#"
-@group(0) @binding(1) var<storage, read> src_buf: array<u32>;
-@group(0) @binding(2) var<storage, read_write> dst_buf: array<u32>;
-
-@compute @workgroup_size(256)
-fn cs_main(@builtin(global_invocation_id) gid: vec3<u32>) {{
-    let i = gid.x;
-    let n = total_cells();
-    if (i >= n) {{ return; }}
-    if (i >= {stride}u) {{
-        dst_buf[i] = src_buf[i] + src_buf[i - {stride}u];
-    }} else {{
-        dst_buf[i] = src_buf[i];
-    }}
-}}
-"#
-    )
-}
-
-/// Exclusive cell offsets from inclusive prefix + raw counts; writes sentinel `cell_offsets[n]`.
-const WGSL_SCAN_EXCLUSIVE: &str = r#"
-@group(0) @binding(1) var<storage, read> counts_for_scan: array<u32>;
-@group(0) @binding(2) var<storage, read> inclusive: array<u32>;
 @group(0) @binding(3) var<storage, read_write> cell_offsets: array<u32>;
 
 @compute @workgroup_size(256)
@@ -201,10 +158,12 @@ fn cs_main(@builtin(global_invocation_id) gid: vec3<u32>) {
     let i = gid.x;
     let n = total_cells();
     if (i == 0u) {
-        cell_offsets[n] = inclusive[n - 1u];
+        cell_offsets[n] = 0u;
     }
     if (i >= n) { return; }
-    cell_offsets[i] = inclusive[i] - counts_for_scan[i];
+    let count = atomicLoad(&cell_atomics[i]);
+    counts_linear[i] = count;
+    cell_offsets[i] = count;
 }
 "#;
 
@@ -244,16 +203,6 @@ fn concat_wgsl(prefix: &str, body: &str) -> String {
     s
 }
 
-/// Inclusive Hillis–Steele step count: `ceil(log2(n))`, `0` for `n <= 1`.
-#[inline]
-fn ceil_log2_u32(n: u32) -> u32 {
-    if n <= 1 {
-        0
-    } else {
-        u32::BITS - (n - 1).leading_zeros()
-    }
-}
-
 /// GPU resources and compute pipelines for one spatial grid configuration.
 pub struct SpatialGridGpu {
     pub params: Handle<wgpu::Buffer>,
@@ -266,28 +215,15 @@ pub struct SpatialGridGpu {
     pub pipeline_clear: Handle<wgpu::ComputePipeline>,
     pub pipeline_count: Handle<wgpu::ComputePipeline>,
     pub pipeline_linearize: Handle<wgpu::ComputePipeline>,
-    pub pipeline_scan: Handle<wgpu::ComputePipeline>,
     pub pipeline_init_heads: Handle<wgpu::ComputePipeline>,
     pub pipeline_scatter: Handle<wgpu::ComputePipeline>,
     pub bind_clear: Handle<wgpu::BindGroup>,
     pub bind_count: Handle<wgpu::BindGroup>,
     pub bind_linearize: Handle<wgpu::BindGroup>,
-    /// Bind group for the exclusive-scan **finalize** pass when inclusive results live in
-    /// `scan_scratch` (same layout as [`Self::bind_scan_finalize_from_temp`]).
-    pub bind_scan: Handle<wgpu::BindGroup>,
     pub bind_init_heads: Handle<wgpu::BindGroup>,
     pub bind_scatter: Handle<wgpu::BindGroup>,
-    bind_scan_copy: Handle<wgpu::BindGroup>,
-    bind_scan_inc_scratch_to_temp: Handle<wgpu::BindGroup>,
-    bind_scan_inc_temp_to_scratch: Handle<wgpu::BindGroup>,
-    bind_scan_finalize_from_temp: Handle<wgpu::BindGroup>,
-    /// Ping-pong buffers for the inclusive scan (handles kept with the grid for lifetime clarity).
-    #[allow(dead_code)]
-    scan_scratch: Handle<wgpu::Buffer>,
-    #[allow(dead_code)]
-    scan_temp: Handle<wgpu::Buffer>,
-    pipeline_scan_copy: Handle<wgpu::ComputePipeline>,
-    pipeline_scan_inclusive_steps: Vec<Handle<wgpu::ComputePipeline>>,
+    /// In-place exclusive scan over the `cells + 1` slots of `cell_offsets`.
+    scan: GpuScan,
     cells: u32,
     max_entities: u32,
 }
@@ -366,20 +302,6 @@ impl SpatialGridGpu {
             .add_usage(wgpu::BufferUsages::COPY_SRC)
             .build(registry)?;
 
-        let scan_scratch = renderer
-            .create_gpu_buffer::<u32>()
-            .label("spatial_grid scan_scratch")
-            .capacity(cells as usize)
-            .usage(BufferUsage::Storage { read_only: false })
-            .build(registry)?;
-
-        let scan_temp = renderer
-            .create_gpu_buffer::<u32>()
-            .label("spatial_grid scan_temp")
-            .capacity(cells as usize)
-            .usage(BufferUsage::Storage { read_only: false })
-            .build(registry)?;
-
         let shader_clear = ShaderModuleBuilder::new(device)
             .label("spatial_grid clear")
             .with_wgsl_source(concat_wgsl(WGSL_PARAMS, WGSL_CLEAR))
@@ -392,15 +314,6 @@ impl SpatialGridGpu {
             .label("spatial_grid linearize")
             .with_wgsl_source(concat_wgsl(WGSL_PARAMS, WGSL_LINEARIZE))
             .build(registry)?;
-        let shader_scan_copy = ShaderModuleBuilder::new(device)
-            .label("spatial_grid scan copy")
-            .with_wgsl_source(concat_wgsl(WGSL_PARAMS, WGSL_SCAN_COPY))
-            .build(registry)?;
-
-        let shader_scan_exclusive = ShaderModuleBuilder::new(device)
-            .label("spatial_grid scan exclusive")
-            .with_wgsl_source(concat_wgsl(WGSL_PARAMS, WGSL_SCAN_EXCLUSIVE))
-            .build(registry)?;
         let shader_init_heads = ShaderModuleBuilder::new(device)
             .label("spatial_grid init_heads")
             .with_wgsl_source(concat_wgsl(WGSL_PARAMS, WGSL_INIT_HEADS))
@@ -503,68 +416,8 @@ impl SpatialGridGpu {
                     },
                     count: None,
                 },
-            ],
-        });
-        let layout_scan_copy = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("spatial_grid scan copy layout"),
-            entries: &[
-                wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: true },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: false },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-            ],
-        });
-        let layout_scan_inc = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-            label: Some("spatial_grid scan inclusive step layout"),
-            entries: &[
                 wgpu::BindGroupLayoutEntry {
-                    binding: 0,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Uniform,
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 1,
-                    visibility: wgpu::ShaderStages::COMPUTE,
-                    ty: wgpu::BindingType::Buffer {
-                        ty: wgpu::BufferBindingType::Storage { read_only: true },
-                        has_dynamic_offset: false,
-                        min_binding_size: None,
-                    },
-                    count: None,
-                },
-                wgpu::BindGroupLayoutEntry {
-                    binding: 2,
+                    binding: 3,
                     visibility: wgpu::ShaderStages::COMPUTE,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Storage { read_only: false },
@@ -575,52 +428,6 @@ impl SpatialGridGpu {
                 },
             ],
         });
-        let layout_scan_finalize =
-            device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
-                label: Some("spatial_grid scan finalize layout"),
-                entries: &[
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 0,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Uniform,
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 1,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Storage { read_only: true },
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 2,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Storage { read_only: true },
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                    wgpu::BindGroupLayoutEntry {
-                        binding: 3,
-                        visibility: wgpu::ShaderStages::COMPUTE,
-                        ty: wgpu::BindingType::Buffer {
-                            ty: wgpu::BufferBindingType::Storage { read_only: false },
-                            has_dynamic_offset: false,
-                            min_binding_size: None,
-                        },
-                        count: None,
-                    },
-                ],
-            });
         let layout_init_heads = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("spatial_grid init_heads layout"),
             entries: &[
@@ -717,21 +524,6 @@ impl SpatialGridGpu {
             bind_group_layouts: &[&layout_linearize],
             push_constant_ranges: &[],
         });
-        let pl_scan_copy = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("spatial_grid scan copy pl"),
-            bind_group_layouts: &[&layout_scan_copy],
-            push_constant_ranges: &[],
-        });
-        let pl_scan_inc = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("spatial_grid scan inclusive pl"),
-            bind_group_layouts: &[&layout_scan_inc],
-            push_constant_ranges: &[],
-        });
-        let pl_scan_finalize = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: Some("spatial_grid scan finalize pl"),
-            bind_group_layouts: &[&layout_scan_finalize],
-            push_constant_ranges: &[],
-        });
         let pl_init_heads = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
             label: Some("spatial_grid init_heads pl"),
             bind_group_layouts: &[&layout_init_heads],
@@ -803,144 +595,6 @@ impl SpatialGridGpu {
                         .unwrap()
                         .as_entire_binding(),
                 },
-            ],
-        });
-        let bind_linearize = registry.insert(bind_linearize);
-
-        let bind_scan_copy = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("spatial_grid bind scan copy"),
-            layout: &layout_scan_copy,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: registry.get(params_handle).unwrap().as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: registry
-                        .get(counts_linear.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: registry
-                        .get(scan_scratch.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-            ],
-        });
-        let bind_scan_copy = registry.insert(bind_scan_copy);
-
-        let bind_scan_inc_scratch_to_temp = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("spatial_grid bind scan inc scratch->temp"),
-            layout: &layout_scan_inc,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: registry.get(params_handle).unwrap().as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: registry
-                        .get(scan_scratch.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: registry
-                        .get(scan_temp.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-            ],
-        });
-        let bind_scan_inc_scratch_to_temp = registry.insert(bind_scan_inc_scratch_to_temp);
-
-        let bind_scan_inc_temp_to_scratch = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("spatial_grid bind scan inc temp->scratch"),
-            layout: &layout_scan_inc,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: registry.get(params_handle).unwrap().as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: registry
-                        .get(scan_temp.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: registry
-                        .get(scan_scratch.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-            ],
-        });
-        let bind_scan_inc_temp_to_scratch = registry.insert(bind_scan_inc_temp_to_scratch);
-
-        let bind_scan_finalize_from_scratch =
-            device.create_bind_group(&wgpu::BindGroupDescriptor {
-                label: Some("spatial_grid bind scan finalize (inclusive in scratch)"),
-                layout: &layout_scan_finalize,
-                entries: &[
-                    wgpu::BindGroupEntry {
-                        binding: 0,
-                        resource: registry.get(params_handle).unwrap().as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 1,
-                        resource: registry
-                            .get(counts_linear.handle())
-                            .unwrap()
-                            .as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 2,
-                        resource: registry
-                            .get(scan_scratch.handle())
-                            .unwrap()
-                            .as_entire_binding(),
-                    },
-                    wgpu::BindGroupEntry {
-                        binding: 3,
-                        resource: registry
-                            .get(cell_offsets.handle())
-                            .unwrap()
-                            .as_entire_binding(),
-                    },
-                ],
-            });
-        let bind_scan_finalize_from_scratch = registry.insert(bind_scan_finalize_from_scratch);
-
-        let bind_scan_finalize_from_temp = device.create_bind_group(&wgpu::BindGroupDescriptor {
-            label: Some("spatial_grid bind scan finalize (inclusive in temp)"),
-            layout: &layout_scan_finalize,
-            entries: &[
-                wgpu::BindGroupEntry {
-                    binding: 0,
-                    resource: registry.get(params_handle).unwrap().as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 1,
-                    resource: registry
-                        .get(counts_linear.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
-                wgpu::BindGroupEntry {
-                    binding: 2,
-                    resource: registry
-                        .get(scan_temp.handle())
-                        .unwrap()
-                        .as_entire_binding(),
-                },
                 wgpu::BindGroupEntry {
                     binding: 3,
                     resource: registry
@@ -950,9 +604,7 @@ impl SpatialGridGpu {
                 },
             ],
         });
-        let bind_scan_finalize_from_temp = registry.insert(bind_scan_finalize_from_temp);
-
-        let bind_scan = bind_scan_finalize_from_scratch;
+        let bind_linearize = registry.insert(bind_linearize);
 
         let bind_init_heads = device.create_bind_group(&wgpu::BindGroupDescriptor {
             label: Some("spatial_grid bind init_heads"),
@@ -1025,34 +677,12 @@ impl SpatialGridGpu {
             .with_layout(pl_linearize)
             .build(registry)?;
 
-        let pipeline_scan_copy = ComputePipelineBuilder::new(device)
-            .with_label("spatial_grid scan copy")
-            .with_compute_shader(shader_scan_copy)
-            .with_layout(pl_scan_copy)
-            .build(registry)?;
-
-        let inclusive_passes = ceil_log2_u32(cells);
-        let mut pipeline_scan_inclusive_steps = Vec::with_capacity(inclusive_passes as usize);
-        for p in 0..inclusive_passes {
-            let stride = 1u32 << p;
-            let wgsl = concat_wgsl(WGSL_PARAMS, &wgsl_scan_inclusive_step(stride));
-            let sm = ShaderModuleBuilder::new(device)
-                .label(&format!("spatial_grid scan inclusive stride {stride}"))
-                .with_wgsl_source(wgsl)
-                .build(registry)?;
-            let pipe = ComputePipelineBuilder::new(device)
-                .with_label(&format!("spatial_grid scan inc {stride}"))
-                .with_compute_shader(sm)
-                .with_layout(pl_scan_inc.clone())
-                .build(registry)?;
-            pipeline_scan_inclusive_steps.push(pipe);
-        }
-
-        let pipeline_scan = ComputePipelineBuilder::new(device)
-            .with_label("spatial_grid scan exclusive")
-            .with_compute_shader(shader_scan_exclusive)
-            .with_layout(pl_scan_finalize)
-            .build(registry)?;
+        let scan = GpuScan::new(
+            renderer,
+            registry,
+            cell_offsets.handle(),
+            cells.saturating_add(1),
+        )?;
 
         let pipeline_init_heads = ComputePipelineBuilder::new(device)
             .with_label("spatial_grid init_heads")
@@ -1077,23 +707,14 @@ impl SpatialGridGpu {
             pipeline_clear,
             pipeline_count,
             pipeline_linearize,
-            pipeline_scan,
             pipeline_init_heads,
             pipeline_scatter,
             bind_clear,
             bind_count,
             bind_linearize,
-            bind_scan,
             bind_init_heads,
             bind_scatter,
-            bind_scan_copy,
-            bind_scan_inc_scratch_to_temp,
-            bind_scan_inc_temp_to_scratch,
-            bind_scan_finalize_from_temp,
-            scan_scratch: scan_scratch.handle(),
-            scan_temp: scan_temp.handle(),
-            pipeline_scan_copy,
-            pipeline_scan_inclusive_steps,
+            scan,
             cells,
             max_entities: cfg.max_entities,
         })
@@ -1116,131 +737,72 @@ impl SpatialGridGpu {
     }
 
     /// Encode the full grid rebuild: clear → count → linearize → scan → init_heads → scatter.
-    pub fn encode_rebuild(&self, encoder: &mut wgpu::CommandEncoder, registry: &ResourceRegistry) {
-        let cells = self.cells;
-        let wg256 = Self::dispatch_1d_256(cells);
+    pub fn encode_rebuild(
+        &self,
+        encoder: &mut wgpu::CommandEncoder,
+        registry: &ResourceRegistry,
+    ) -> SpatialGridResult<()> {
+        let wg256 = Self::dispatch_1d_256(self.cells);
         let wg_ent = Self::dispatch_1d_256(self.max_entities);
+        let stage = |label: &'static str, pipeline, bind_group, workgroups| {
+            let pipeline = registry
+                .get(pipeline)
+                .ok_or(SpatialGridError::MissingResource(label))?;
+            let bind_group = registry
+                .get(bind_group)
+                .ok_or(SpatialGridError::MissingResource(label))?;
+            Ok::<_, SpatialGridError>((label, pipeline, bind_group, workgroups))
+        };
+        let before_scan = [
+            stage(
+                "spatial_grid clear",
+                self.pipeline_clear,
+                self.bind_clear,
+                wg256,
+            )?,
+            stage(
+                "spatial_grid count",
+                self.pipeline_count,
+                self.bind_count,
+                wg_ent,
+            )?,
+            stage(
+                "spatial_grid linearize",
+                self.pipeline_linearize,
+                self.bind_linearize,
+                wg256,
+            )?,
+        ];
+        let after_scan = [
+            stage(
+                "spatial_grid init_heads",
+                self.pipeline_init_heads,
+                self.bind_init_heads,
+                wg256,
+            )?,
+            stage(
+                "spatial_grid scatter",
+                self.pipeline_scatter,
+                self.bind_scatter,
+                wg_ent,
+            )?,
+        ];
 
-        let p_clear = registry.get(self.pipeline_clear).expect("pipeline_clear");
-        let p_count = registry.get(self.pipeline_count).expect("pipeline_count");
-        let p_linearize = registry
-            .get(self.pipeline_linearize)
-            .expect("pipeline_linearize");
-        let p_scan_copy = registry
-            .get(self.pipeline_scan_copy)
-            .expect("pipeline_scan_copy");
-        let p_scan = registry.get(self.pipeline_scan).expect("pipeline_scan");
-        let p_init = registry
-            .get(self.pipeline_init_heads)
-            .expect("pipeline_init_heads");
-        let p_scatter = registry
-            .get(self.pipeline_scatter)
-            .expect("pipeline_scatter");
-
-        let g_clear = registry.get(self.bind_clear).expect("bind_clear");
-        let g_count = registry.get(self.bind_count).expect("bind_count");
-        let g_linearize = registry.get(self.bind_linearize).expect("bind_linearize");
-        let g_scan_copy = registry.get(self.bind_scan_copy).expect("bind_scan_copy");
-        let g_scan_inc_s2t = registry
-            .get(self.bind_scan_inc_scratch_to_temp)
-            .expect("bind_scan_inc_s2t");
-        let g_scan_inc_t2s = registry
-            .get(self.bind_scan_inc_temp_to_scratch)
-            .expect("bind_scan_inc_t2s");
-        let g_scan_fin_scratch = registry
-            .get(self.bind_scan)
-            .expect("bind_scan (finalize scratch)");
-        let g_scan_fin_temp = registry
-            .get(self.bind_scan_finalize_from_temp)
-            .expect("bind_scan_fin_temp");
-        let g_init = registry.get(self.bind_init_heads).expect("bind_init_heads");
-        let g_scatter = registry.get(self.bind_scatter).expect("bind_scatter");
-
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid clear"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_clear);
-            pass.set_bind_group(0, g_clear, &[]);
-            pass.dispatch_workgroups(wg256, 1, 1);
-        }
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid count"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_count);
-            pass.set_bind_group(0, g_count, &[]);
-            pass.dispatch_workgroups(wg_ent, 1, 1);
-        }
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid linearize"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_linearize);
-            pass.set_bind_group(0, g_linearize, &[]);
-            pass.dispatch_workgroups(wg256, 1, 1);
-        }
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid scan copy"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_scan_copy);
-            pass.set_bind_group(0, g_scan_copy, &[]);
-            pass.dispatch_workgroups(wg256, 1, 1);
-        }
-        let mut inclusive_in_scratch = true;
-        for pipe_h in &self.pipeline_scan_inclusive_steps {
-            let p_inc = registry.get(*pipe_h).expect("pipeline_scan_inclusive");
-            let g_inc = if inclusive_in_scratch {
-                g_scan_inc_s2t
-            } else {
-                g_scan_inc_t2s
-            };
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid scan inclusive"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_inc);
-            pass.set_bind_group(0, g_inc, &[]);
-            pass.dispatch_workgroups(wg256, 1, 1);
-            inclusive_in_scratch = !inclusive_in_scratch;
-        }
-        let g_scan_fin = if inclusive_in_scratch {
-            g_scan_fin_scratch
-        } else {
-            g_scan_fin_temp
+        let encode_stages = |encoder: &mut wgpu::CommandEncoder, stages: &[_]| {
+            for &(label, pipeline, bind_group, workgroups) in stages {
+                let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
+                    label: Some(label),
+                    timestamp_writes: None,
+                });
+                pass.set_pipeline(pipeline);
+                pass.set_bind_group(0, bind_group, &[]);
+                pass.dispatch_workgroups(workgroups, 1, 1);
+            }
         };
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid scan exclusive"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_scan);
-            pass.set_bind_group(0, g_scan_fin, &[]);
-            pass.dispatch_workgroups(wg256, 1, 1);
-        }
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid init_heads"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_init);
-            pass.set_bind_group(0, g_init, &[]);
-            pass.dispatch_workgroups(wg256, 1, 1);
-        }
-        {
-            let mut pass = encoder.begin_compute_pass(&wgpu::ComputePassDescriptor {
-                label: Some("spatial_grid scatter"),
-                timestamp_writes: None,
-            });
-            pass.set_pipeline(p_scatter);
-            pass.set_bind_group(0, g_scatter, &[]);
-            pass.dispatch_workgroups(wg_ent, 1, 1);
-        }
+        encode_stages(encoder, &before_scan);
+        self.scan.encode(encoder, registry)?;
+        encode_stages(encoder, &after_scan);
+        Ok(())
     }
 }
 
@@ -1326,7 +888,8 @@ mod tests {
                 .create_command_encoder(&wgpu::CommandEncoderDescriptor {
                     label: Some("spatial_grid test"),
                 });
-        grid.encode_rebuild(&mut encoder, &registry);
+        grid.encode_rebuild(&mut encoder, &registry)
+            .expect("encode rebuild");
 
         let src_counts = registry.get(grid.counts_linear).expect("counts");
         let dst_counts = registry.get(rb_counts.handle()).expect("rb_counts");
@@ -1362,8 +925,7 @@ mod tests {
         let offsets = renderer
             .read_buffer::<u32>(rb_offsets.handle(), &registry)
             .expect("read offsets");
-        assert_eq!(offsets.len(), 9);
-        assert_eq!(offsets[8], 4);
+        assert_eq!(offsets, vec![0, 1, 2, 3, 3, 3, 3, 3, 4]);
 
         let sorted = renderer
             .read_buffer::<u32>(rb_sorted.handle(), &registry)
@@ -1,13 +1,76 @@
-use wgpu::{Surface, SurfaceConfiguration, SurfaceTexture, TextureFormat};
+use wgpu::{PresentMode, Surface, SurfaceConfiguration, SurfaceTexture, TextureFormat};
+
+/// High-level presentation preference, resolved against the modes a surface supports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PresentModePreference {
+    /// Tear-free presentation capped at the display refresh rate.
+    #[default]
+    Vsync,
+    /// Tear-free, low-latency presentation; falls back to vsync.
+    Mailbox,
+    /// Uncapped presentation that may tear; falls back to mailbox, then vsync.
+    Immediate,
+}
+
+impl PresentModePreference {
+    /// Present modes to try for this preference, most preferred first.
+    pub fn candidates(self) -> &'static [PresentMode] {
+        match self {
+            PresentModePreference::Vsync => &[PresentMode::Fifo, PresentMode::FifoRelaxed],
+            PresentModePreference::Mailbox => &[PresentMode::Mailbox, PresentMode::Fifo],
+            PresentModePreference::Immediate => &[
+                PresentMode::Immediate,
+                PresentMode::Mailbox,
+                PresentMode::Fifo,
+            ],
+        }
+    }
+
+    /// Pick the first candidate contained in `supported`.
+    /// Falls back to the crate default when none match.
+    pub fn resolve(self, supported: &[PresentMode]) -> PresentMode {
+        self.candidates()
+            .iter()
+            .copied()
+            .find(|mode| supported.contains(mode))
+            .unwrap_or_else(|| resolve_present_mode(PresentMode::AutoVsync, supported))
+    }
+}
+
+/// Resolve an explicitly requested present mode against the supported set.
+///
+/// The `Auto*` modes are always accepted because wgpu resolves them itself.
+/// Unsupported explicit modes fall back to the default vsync-first ordering.
+pub(crate) fn resolve_present_mode(
+    requested: PresentMode,
+    supported: &[PresentMode],
+) -> PresentMode {
+    match requested {
+        PresentMode::AutoVsync | PresentMode::AutoNoVsync => requested,
+        _ if supported.contains(&requested) || supported.is_empty() => requested,
+        _ => crate::pick_default_present_mode(supported),
+    }
+}
 
 /// Wrapper around wgpu::Surface with configuration management
 pub struct SurfaceWrapper {
     surface: Surface<'static>,
     config: SurfaceConfiguration,
+    supported_present_modes: Vec<PresentMode>,
 }
 impl SurfaceWrapper {
-    pub fn new(surface: Surface<'static>, config: SurfaceConfiguration) -> Self {
-        Self { surface, config }
+    /// Wrap a configured surface. `supported_present_modes` should come from
+    /// the surface capabilities; preferences are resolved against it.
+    pub fn new(
+        surface: Surface<'static>,
+        config: SurfaceConfiguration,
+        supported_present_modes: Vec<PresentMode>,
+    ) -> Self {
+        Self {
+            surface,
+            config,
+            supported_present_modes,
+        }
     }
 
     pub fn format(&self) -> TextureFormat {
@@ -26,14 +89,123 @@ impl SurfaceWrapper {
         &self.config
     }
 
+    pub fn present_mode(&self) -> PresentMode {
+        self.config.present_mode
+    }
+
+    pub fn supported_present_modes(&self) -> &[PresentMode] {
+        &self.supported_present_modes
+    }
+
     pub fn reconfigure(&mut self, device: &wgpu::Device, config: SurfaceConfiguration) {
         self.config = config;
         self.surface.configure(device, &self.config);
     }
 
+    /// Reapply the current configuration.
+    /// Use after `SurfaceError::Lost` or `SurfaceError::Outdated` to recreate the swapchain.
+    pub fn configure(&self, device: &wgpu::Device) {
+        self.surface.configure(device, &self.config);
+    }
+
+    /// Resize the swapchain.
+    /// Returns `false` without touching the surface when either dimension is zero
+    /// (e.g. a minimized window), since a zero-sized surface cannot be configured.
+    pub fn resize(&mut self, device: &wgpu::Device, width: u32, height: u32) -> bool {
+        if width == 0 || height == 0 {
+            return false;
+        }
+        self.config.width = width;
+        self.config.height = height;
+        self.surface.configure(device, &self.config);
+        true
+    }
+
+    /// Switch to `present_mode`, falling back to a supported mode if necessary.
+    /// Returns the mode that was actually configured.
+    pub fn set_present_mode(
+        &mut self,
+        device: &wgpu::Device,
+        present_mode: PresentMode,
+    ) -> PresentMode {
+        let resolved = resolve_present_mode(present_mode, &self.supported_present_modes);
+        if resolved != self.config.present_mode {
+            self.config.present_mode = resolved;
+            self.surface.configure(device, &self.config);
+        }
+        resolved
+    }
+
+    /// Switch to the best supported mode for `preference`.
+    /// Returns the mode that was actually configured.
+    pub fn set_present_mode_preference(
+        &mut self,
+        device: &wgpu::Device,
+        preference: PresentModePreference,
+    ) -> PresentMode {
+        let resolved = preference.resolve(&self.supported_present_modes);
+        self.set_present_mode(device, resolved)
+    }
+
     /// Get the current surface texture for rendering
     /// Returns an error if the surface is lost or needs to be recreated
     pub fn get_current_texture(&self) -> Result<SurfaceTexture, wgpu::SurfaceError> {
         self.surface.get_current_texture()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_preference_prefers_first_supported_candidate() {
+        let supported = [
+            PresentMode::Fifo,
+            PresentMode::Mailbox,
+            PresentMode::Immediate,
+        ];
+        assert_eq!(
+            PresentModePreference::Vsync.resolve(&supported),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            PresentModePreference::Mailbox.resolve(&supported),
+            PresentMode::Mailbox
+        );
+        assert_eq!(
+            PresentModePreference::Immediate.resolve(&supported),
+            PresentMode::Immediate
+        );
+    }
+
+    #[test]
+    fn test_preference_falls_back_when_unsupported() {
+        let fifo_only = [PresentMode::Fifo];
+        assert_eq!(
+            PresentModePreference::Mailbox.resolve(&fifo_only),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            PresentModePreference::Immediate.resolve(&[PresentMode::Mailbox, PresentMode::Fifo]),
+            PresentMode::Mailbox
+        );
+    }
+
+    #[test]
+    fn test_resolve_explicit_mode() {
+        let supported = [PresentMode::Fifo, PresentMode::Immediate];
+        assert_eq!(
+            resolve_present_mode(PresentMode::Immediate, &supported),
+            PresentMode::Immediate
+        );
+        assert_eq!(
+            resolve_present_mode(PresentMode::Mailbox, &supported),
+            PresentMode::Fifo
+        );
+        assert_eq!(
+            resolve_present_mode(PresentMode::AutoNoVsync, &supported),
+            PresentMode::AutoNoVsync
+        );
+    }
+}
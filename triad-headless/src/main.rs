@@ -46,7 +46,7 @@ fn run_demo() -> Result<(), Box<dyn Error>> {
 
     let bytes = renderer.read_buffer::<u8>(sim.readback_handle(), &registry)?;
     sim.validate_red_image(&bytes)
-        .map_err(|err| std::io::Error::other(err))?;
+        .map_err(std::io::Error::other)?;
     let output_path = output_path_from_env();
     write_png(&bytes, sim.config(), &output_path)?;
 
@@ -1,3 +1,7 @@
+// The C entry points keep safe signatures for ctypes; each one null-checks its
+// pointers and the Python wrapper owns buffer lengths and handle lifetimes.
+#![allow(clippy::not_unsafe_ptr_arg_deref)]
+
 use std::ffi::{CStr, c_char};
 use std::panic::{AssertUnwindSafe, catch_unwind};
 use std::ptr;
@@ -1,7 +1,7 @@
 use triad_gpu::{
     BindGroupError, BindingType, BufferCopy, BufferError, BufferUsage, ComputePassBuilder,
     CopyPassBuilder, ExecutableFrameGraph, FrameGraph, Renderer, ResourceRegistry, Result,
-    ShaderStage, wgpu,
+    ShaderStage, wgpu, workgroup_count,
 };
 
 use crate::course::{CompiledCourse, CourseSpec, GpuCourseHeader, GpuStageSpec};
@@ -2716,9 +2716,10 @@ impl GpuSimulation {
 }
 
 fn dispatch_count(env_count: usize) -> u32 {
-    ((env_count as u32) + SIM_WORKGROUP_SIZE - 1) / SIM_WORKGROUP_SIZE
+    workgroup_count(env_count as u32, SIM_WORKGROUP_SIZE)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_step_graph(
     pipeline: triad_gpu::Handle<wgpu::ComputePipeline>,
     bind_group: triad_gpu::Handle<wgpu::BindGroup>,
@@ -5,22 +5,26 @@ use std::path::PathBuf;
 use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
 use std::sync::{Arc, Mutex};
 
-use glam::Vec3;
+use glam::{Mat4, Vec3};
 use serde::{Deserialize, Serialize};
 use tracing::info;
 use triad_gpu::{
-    BindingType, BufferUsage, ColorLoadOp, DepthLoadOp, ExecutableFrameGraph, FrameGraphError,
-    FrameTextureView, RenderPassBuilder, Renderer, ResourceRegistry, ShaderStage, wgpu,
+    BackgroundConfig, BackgroundRenderer, BindingType, BufferUsage, ColorLoadOp, DebugLineRenderer,
+    DebugLines, DepthMode, ExecutableFrameGraph, FrameGraphError, FrameTextureView, GroundGrid,
+    RenderPassBuilder, Renderer, ResourceRegistry, ShaderStage, wgpu,
 };
 use triad_sim::{
     Action, CourseSpec, EnvLayoutHeader, EnvState, Gate, GpuSimulation, GpuSimulationConfig,
     Observation, ResetParams, RewardDone,
 };
 use triad_window::{
-    CameraPose, CameraUniforms, RendererManager, WindowConfig, egui, run_with_renderer_config,
+    Annotation, CameraPose, CameraUniforms, RendererConfig, RendererManager, SceneBounds,
+    WindowConfig, egui, run_with_renderer_config,
 };
 
 const WINDOW_TITLE: &str = "Triad Visualizer";
+/// Reverse-Z keeps distant gates and the arena floor free of z-fighting.
+const DEPTH_MODE: DepthMode = DepthMode::Reversed;
 // Keep this in sync with the sim default until min_altitude is exposed publicly.
 const FLOOR_ALTITUDE: f32 = 0.1;
 const FLOOR_HALF_THICKNESS: f32 = 0.02;
@@ -56,6 +60,9 @@ const DONE_REASON_OUT_OF_BOUNDS: u32 = 1 << 4;
 const DONE_REASON_STEP_LIMIT: u32 = 1 << 5;
 const DONE_REASON_EXCESSIVE_TILT: u32 = 1 << 6;
 
+/// Per-environment actions and value estimates returned by the policy server.
+type PolicyPrediction = (Vec<[f32; 4]>, Vec<f32>);
+
 const VISUALIZER_SHADER: &str = r#"
 struct CameraUniforms {
     view_matrix: mat4x4<f32>,
@@ -73,14 +80,22 @@ struct RenderInstance {
     color: vec4<f32>,
 };
 
+struct SceneEnvironment {
+    /// rgb = fog color, a = fog density per world unit of view distance.
+    fog: vec4<f32>,
+    ambient_tint: vec4<f32>,
+};
+
 struct VsOut {
     @builtin(position) clip_pos: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) light: f32,
+    @location(2) view_distance: f32,
 };
 
 @group(0) @binding(0) var<uniform> camera_u: CameraUniforms;
 @group(0) @binding(1) var<storage, read> instances: array<RenderInstance>;
+@group(0) @binding(2) var<uniform> environment: SceneEnvironment;
 
 const POSITIONS: array<vec3<f32>, 36> = array<vec3<f32>, 36>(
     vec3<f32>(-1.0, -1.0,  1.0), vec3<f32>( 1.0, -1.0,  1.0), vec3<f32>( 1.0,  1.0,  1.0),
@@ -137,13 +152,16 @@ fn vs_main(
         camera_u.proj_matrix * camera_u.view_matrix * vec4<f32>(world_position, 1.0);
     out.color = instance.color;
     out.light = max(dot(world_normal, light_dir), 0.0);
+    out.view_distance = distance(world_position, camera_u.view_pos);
     return out;
 }
 
 @fragment
 fn fs_main(in: VsOut) -> @location(0) vec4<f32> {
     let lit = 0.3 + 0.7 * in.light;
-    return vec4<f32>(in.color.rgb * lit, in.color.a);
+    let base = in.color.rgb * lit * environment.ambient_tint.rgb;
+    let fog = 1.0 - exp(-environment.fog.a * in.view_distance);
+    return vec4<f32>(mix(base, environment.fog.rgb, fog), in.color.a);
 }
 "#;
 
@@ -189,9 +207,50 @@ impl RenderInstance {
     }
 }
 
+/// Fog and ambient tint applied by the box shader.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct SceneEnvironment {
+    fog_color: [f32; 3],
+    /// Fog density per world unit of distance from the camera; 0 disables fog.
+    fog_density: f32,
+    ambient_tint: [f32; 3],
+}
+
+impl Default for SceneEnvironment {
+    fn default() -> Self {
+        Self {
+            fog_color: [0.16, 0.18, 0.22],
+            fog_density: 0.0,
+            ambient_tint: [1.0, 1.0, 1.0],
+        }
+    }
+}
+
+impl SceneEnvironment {
+    fn params(&self) -> SceneEnvironmentParams {
+        let [fr, fg, fb] = self.fog_color;
+        let [ar, ag, ab] = self.ambient_tint;
+        SceneEnvironmentParams {
+            fog: [fr, fg, fb, self.fog_density.max(0.0)],
+            ambient_tint: [ar, ag, ab, 1.0],
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, bytemuck::Pod, bytemuck::Zeroable)]
+struct SceneEnvironmentParams {
+    /// rgb = fog color, w = density.
+    fog: [f32; 4],
+    ambient_tint: [f32; 4],
+}
+
 #[derive(Debug)]
 struct UiState {
     replay_active: bool,
+    show_debug_geometry: bool,
+    show_ground_grid: bool,
+    environment: SceneEnvironment,
     use_checkpoint_policy: bool,
     selected_env: usize,
     difficulty: f32,
@@ -224,6 +283,9 @@ impl Default for UiState {
     fn default() -> Self {
         Self {
             replay_active: false,
+            show_debug_geometry: false,
+            show_ground_grid: true,
+            environment: SceneEnvironment::default(),
             use_checkpoint_policy: false,
             selected_env: 0,
             difficulty: 0.35,
@@ -257,6 +319,9 @@ impl Default for UiState {
 #[derive(Debug, Clone)]
 struct UiSnapshot {
     replay_active: bool,
+    show_debug_geometry: bool,
+    show_ground_grid: bool,
+    environment: SceneEnvironment,
     use_checkpoint_policy: bool,
     selected_env: usize,
     difficulty: f32,
@@ -335,7 +400,7 @@ impl PpoPolicyClient {
     fn predict(
         &mut self,
         observations: &[Observation],
-    ) -> Result<(Vec<[f32; 4]>, Vec<f32>), Box<dyn Error>> {
+    ) -> Result<PolicyPrediction, Box<dyn Error>> {
         let request = PpoPolicyRequest {
             observations: observations.iter().map(flatten_observation).collect(),
             deterministic: true,
@@ -385,10 +450,22 @@ struct VisualizerManager {
     ui_state: Arc<Mutex<UiState>>,
     camera_buffer: triad_gpu::Handle<wgpu::Buffer>,
     instance_buffer: triad_gpu::Handle<wgpu::Buffer>,
+    environment_buffer: triad_gpu::Handle<wgpu::Buffer>,
+    applied_environment: SceneEnvironment,
     render_bind_group: triad_gpu::Handle<wgpu::BindGroup>,
     render_pipeline: triad_gpu::Handle<wgpu::RenderPipeline>,
     frame_target: triad_gpu::Handle<FrameTextureView>,
     depth_frame: triad_gpu::Handle<FrameTextureView>,
+    background: BackgroundRenderer,
+    show_ground_grid: bool,
+    debug_lines: DebugLineRenderer,
+    debug_batch: DebugLines,
+    gate_labels: Vec<Annotation>,
+    show_debug_geometry: bool,
+    /// Debug line vertices drawn by the cached frame graph.
+    graph_debug_vertex_count: u32,
+    /// Set when a toggle or baked draw count changes; reported from `prepare_frame`.
+    graph_dirty: bool,
     cached_layouts: Vec<EnvLayoutHeader>,
     cached_gates: Vec<Gate>,
     cached_states: Vec<EnvState>,
@@ -443,6 +520,13 @@ impl VisualizerManager {
             .with_data(&hidden_instances)
             .build(registry)?;
 
+        let environment_buffer = renderer
+            .create_gpu_buffer::<SceneEnvironmentParams>()
+            .label("visualizer environment")
+            .with_data(&[SceneEnvironment::default().params()])
+            .usage(BufferUsage::Uniform)
+            .build(registry)?;
+
         let shader = renderer
             .create_shader_module()
             .label("visualizer boxes")
@@ -464,6 +548,12 @@ impl VisualizerManager {
                 instance_buffer.handle(),
                 BindingType::StorageRead,
             )
+            .buffer_stage(
+                2,
+                ShaderStage::Fragment,
+                environment_buffer.handle(),
+                BindingType::Uniform,
+            )
             .build(registry)?;
 
         let render_pipeline_layout =
@@ -497,6 +587,7 @@ impl VisualizerManager {
                 blend: Some(wgpu::BlendState::REPLACE),
                 write_mask: wgpu::ColorWrites::ALL,
             }))
+            .with_depth_mode(DEPTH_MODE)
             .with_depth_stencil(wgpu::DepthStencilState {
                 format: wgpu::TextureFormat::Depth32Float,
                 depth_write_enabled: true,
@@ -506,6 +597,22 @@ impl VisualizerManager {
             })
             .build(registry)?;
 
+        let background = BackgroundRenderer::new(
+            renderer,
+            registry,
+            surface_format,
+            wgpu::TextureFormat::Depth32Float,
+            DEPTH_MODE,
+            BackgroundConfig::default(),
+        )?;
+        let debug_lines = DebugLineRenderer::new(
+            renderer,
+            registry,
+            surface_format,
+            Some(wgpu::TextureFormat::Depth32Float),
+            DEPTH_MODE,
+        )?;
+
         let zero_actions = vec![Action::idle(); sim.env_count()];
         let trail_points = (0..sim.env_count())
             .map(|_| VecDeque::with_capacity(TRAIL_MAX_POINTS))
@@ -516,11 +623,21 @@ impl VisualizerManager {
             sim,
             ui_state,
             camera_buffer: camera_buffer.handle(),
+            environment_buffer: environment_buffer.handle(),
+            applied_environment: SceneEnvironment::default(),
             instance_buffer: instance_buffer.handle(),
             render_bind_group,
             render_pipeline,
             frame_target: registry.insert(FrameTextureView::new()),
             depth_frame: registry.insert(FrameTextureView::new()),
+            background,
+            show_ground_grid: true,
+            debug_lines,
+            debug_batch: DebugLines::new(),
+            gate_labels: Vec::new(),
+            show_debug_geometry: false,
+            graph_debug_vertex_count: 0,
+            graph_dirty: false,
             cached_layouts: Vec::new(),
             cached_gates: Vec::new(),
             cached_states: Vec::new(),
@@ -542,6 +659,9 @@ impl VisualizerManager {
         let mut state = self.ui_state.lock().expect("ui state poisoned");
         let snapshot = UiSnapshot {
             replay_active: state.replay_active,
+            show_debug_geometry: state.show_debug_geometry,
+            show_ground_grid: state.show_ground_grid,
+            environment: state.environment,
             use_checkpoint_policy: state.use_checkpoint_policy,
             selected_env: state
                 .selected_env
@@ -677,10 +797,10 @@ impl VisualizerManager {
         self.actions.fill(Action::idle());
         self.actions[selected_env] = Action::new(action_rows[0]);
         self.last_value_estimates.fill(0.0);
-        if let Some(value) = values.first().copied() {
-            if let Some(slot) = self.last_value_estimates.get_mut(selected_env) {
-                *slot = value;
-            }
+        if let Some(value) = values.first().copied()
+            && let Some(slot) = self.last_value_estimates.get_mut(selected_env)
+        {
+            *slot = value;
         }
         self.set_checkpoint_status("Replay active with PPO checkpoint (selected env)");
         Ok(())
@@ -718,11 +838,10 @@ impl VisualizerManager {
                 .cached_gates
                 .get((layout.obstacle_offset as usize) + obstacle_index)
                 .copied()
+                && let Some(slot) = self.instances.get_mut(write_index)
             {
-                if let Some(slot) = self.instances.get_mut(write_index) {
-                    *slot = obstacle_instance(obstacle);
-                    write_index += 1;
-                }
+                *slot = obstacle_instance(obstacle);
+                write_index += 1;
             }
         }
 
@@ -748,14 +867,72 @@ impl VisualizerManager {
                     write_index += 1;
                 }
             }
-            if let Some(target_gate) = target_gate {
-                if let Some(slot) = self.instances.get_mut(write_index) {
-                    *slot = target_instance(target_gate);
-                }
+            if let Some(target_gate) = target_gate
+                && let Some(slot) = self.instances.get_mut(write_index)
+            {
+                *slot = target_instance(target_gate);
             }
         }
     }
 
+    /// Bounds of one gate, padded to cover its frame at any yaw.
+    fn gate_bounds(gate: &Gate) -> SceneBounds {
+        let radius = gate.half_extents[0].max(gate.half_extents[2]) + GATE_FRAME_THICKNESS;
+        let half = Vec3::new(radius, gate.half_extents[1] + GATE_FRAME_THICKNESS, radius);
+        let center = vec3_from_array(gate.center);
+        SceneBounds::new(center - half, center + half)
+    }
+
+    /// Gates of the selected env.
+    fn selected_gates(&self) -> &[Gate] {
+        self.cached_layouts
+            .get(self.selected_env)
+            .and_then(|layout| {
+                let start = layout.gate_offset as usize;
+                self.cached_gates
+                    .get(start..start + layout.gate_count as usize)
+            })
+            .unwrap_or_default()
+    }
+
+    /// The arena floor plus every gate of the selected env.
+    fn selected_scene_bounds(&self) -> SceneBounds {
+        let bounds = self.sim.config().bounds;
+        let floor = SceneBounds::new(
+            Vec3::new(-bounds, FLOOR_ALTITUDE, -bounds),
+            Vec3::new(bounds, FLOOR_ALTITUDE, bounds),
+        );
+        self.selected_gates()
+            .iter()
+            .fold(floor, |scene, gate| scene.union(&Self::gate_bounds(gate)))
+    }
+
+    /// World axes, the scene bounds, and per-gate bounds and labels of the selected env.
+    fn rebuild_debug_geometry(&mut self) {
+        self.debug_batch.clear();
+        self.gate_labels.clear();
+        if !self.show_debug_geometry {
+            return;
+        }
+        self.debug_batch.axes(Vec3::ZERO, 1.0);
+        let gates: Vec<SceneBounds> = self
+            .selected_gates()
+            .iter()
+            .map(Self::gate_bounds)
+            .collect();
+        for (index, gate) in gates.into_iter().enumerate() {
+            self.debug_batch
+                .aabb(gate.min, gate.max, [0.55, 0.6, 0.7, 0.6]);
+            let top = Vec3::new(gate.center().x, gate.max.y, gate.center().z);
+            self.gate_labels.push(
+                Annotation::new(top, format!("Gate {index}")).with_color([0.8, 0.85, 0.95, 1.0]),
+            );
+        }
+        let scene = self.selected_scene_bounds();
+        self.debug_batch
+            .aabb(scene.min, scene.max, [1.0, 0.85, 0.25, 1.0]);
+    }
+
     fn update_ui_snapshot(
         &self,
         selected_state: Option<EnvState>,
@@ -867,6 +1044,14 @@ impl RendererManager for VisualizerManager {
 
         let snapshot = self.snapshot_ui();
         self.selected_env = snapshot.selected_env;
+        if snapshot.environment != self.applied_environment {
+            renderer.write_buffer(
+                self.environment_buffer,
+                &[snapshot.environment.params()],
+                registry,
+            )?;
+            self.applied_environment = snapshot.environment;
+        }
         let generation_changed = (snapshot.difficulty - self.applied_difficulty).abs() > 1e-5
             || snapshot.curriculum_stage != self.applied_curriculum_stage;
 
@@ -983,6 +1168,33 @@ impl RendererManager for VisualizerManager {
         }
         self.rebuild_instances(selected_state);
         renderer.write_buffer(self.instance_buffer, &self.instances, registry)?;
+
+        let view = Mat4::from_cols_array_2d(&camera.view_matrix);
+        let view_proj = Mat4::from_cols_array_2d(&camera.proj_matrix) * view;
+        if self.show_ground_grid != snapshot.show_ground_grid {
+            self.show_ground_grid = snapshot.show_ground_grid;
+            self.graph_dirty = true;
+        }
+        self.background.upload(
+            renderer,
+            registry,
+            view_proj,
+            view.inverse().w_axis.truncate(),
+        )?;
+
+        if self.show_debug_geometry != snapshot.show_debug_geometry {
+            self.show_debug_geometry = snapshot.show_debug_geometry;
+            self.graph_dirty = true;
+        }
+        self.rebuild_debug_geometry();
+        self.debug_lines
+            .upload(renderer, registry, view_proj, &self.debug_batch)?;
+        // The line pass bakes its vertex count, so a new count needs a new graph.
+        if self.show_debug_geometry
+            && self.debug_lines.vertex_count() != self.graph_debug_vertex_count
+        {
+            self.graph_dirty = true;
+        }
         self.update_ui_snapshot(selected_state, selected_observation, selected_reward_done);
 
         Ok(())
@@ -1004,37 +1216,61 @@ impl RendererManager for VisualizerManager {
                 .expect("visualizer depth target should exist")
                 .set(depth);
         }
-        Ok(false)
+        Ok(std::mem::take(&mut self.graph_dirty))
     }
 
     fn build_frame_graph(&mut self) -> Result<ExecutableFrameGraph, FrameGraphError> {
+        let sky_pass = self
+            .background
+            .sky_pass("VisualizerSky", self.frame_target)?;
         let render_pass = RenderPassBuilder::new("VisualizerRender")
             .with_pipeline(self.render_pipeline)
             .with_bind_group(0, self.render_bind_group)
-            .with_frame_color_attachment(
-                self.frame_target,
-                ColorLoadOp::Clear(wgpu::Color {
-                    r: 0.07,
-                    g: 0.08,
-                    b: 0.11,
-                    a: 1.0,
-                }),
-            )
+            .with_frame_color_attachment(self.frame_target, ColorLoadOp::Load)
             .with_frame_depth_stencil_attachment(
                 self.depth_frame,
-                DepthLoadOp::Clear(1.0),
+                DEPTH_MODE.clear(),
                 wgpu::StoreOp::Store,
                 None,
             )
             .draw(36, self.instances.len() as u32)
-            .build()
-            .expect("visualizer render pass should build");
+            .build()?;
 
         let mut graph = triad_gpu::FrameGraph::new();
+        graph.add_pass(sky_pass);
         graph.add_pass(render_pass);
+        if self.show_ground_grid
+            && let Some(grid_pass) =
+                self.background
+                    .grid_pass("VisualizerGrid", self.frame_target, self.depth_frame)
+        {
+            graph.add_pass(grid_pass?);
+        }
+        if self.show_debug_geometry {
+            let debug_pass = self.debug_lines.pass(
+                "VisualizerDebugLines",
+                self.frame_target,
+                Some(self.depth_frame),
+            )?;
+            graph.add_pass(debug_pass);
+            self.graph_debug_vertex_count = self.debug_lines.vertex_count();
+        }
         graph.build()
     }
 
+    fn scene_bounds(&self) -> Option<SceneBounds> {
+        Some(self.selected_scene_bounds())
+    }
+
+    fn annotations(&self) -> &[Annotation] {
+        &self.gate_labels
+    }
+
+    fn set_background(&mut self, background: BackgroundConfig) {
+        self.background.set_config(background);
+        self.graph_dirty = true;
+    }
+
     fn resize(
         &mut self,
         _device: &wgpu::Device,
@@ -1055,7 +1291,13 @@ fn main() -> Result<(), Box<dyn Error>> {
 
     run_with_renderer_config(
         WINDOW_TITLE,
-        WindowConfig::default(),
+        WindowConfig::default()
+            .with_renderer_config(
+                RendererConfig::default()
+                    .with_background(visualizer_background(GpuSimulationConfig::default().bounds)),
+            )
+            .with_depth_mode(DEPTH_MODE)
+            .with_infinite_far(true),
         move |controls| {
             controls.request_reset(CameraPose::new(
                 Vec3::new(8.5, 5.5, 8.5),
@@ -1068,6 +1310,26 @@ fn main() -> Result<(), Box<dyn Error>> {
                     .default_pos(egui::pos2(12.0, 84.0))
                     .show(ctx, |panel| {
                         panel.checkbox(&mut ui.replay_active, "Replay Active");
+                        panel.checkbox(&mut ui.show_ground_grid, "Ground Grid");
+                        panel.checkbox(&mut ui.show_debug_geometry, "Debug Geometry");
+                        panel.collapsing("Environment", |section| {
+                            let environment = &mut ui.environment;
+                            section.horizontal(|row| {
+                                row.label("Ambient Tint");
+                                row.color_edit_button_rgb(&mut environment.ambient_tint);
+                            });
+                            section.horizontal(|row| {
+                                row.label("Fog Color");
+                                row.color_edit_button_rgb(&mut environment.fog_color);
+                            });
+                            section.add(
+                                egui::Slider::new(&mut environment.fog_density, 0.0..=0.2)
+                                    .text("Fog Density"),
+                            );
+                            if section.button("Reset Environment").clicked() {
+                                *environment = SceneEnvironment::default();
+                            }
+                        });
                         panel.checkbox(&mut ui.use_checkpoint_policy, "Use Checkpoint Policy");
                         panel.horizontal(|row| {
                             row.label("Checkpoint");
@@ -1307,6 +1569,26 @@ fn gate_bar_instances(gate: Gate) -> [RenderInstance; 4] {
     ]
 }
 
+/// Gradient sky plus a grid laid just above the floor slab.
+fn visualizer_background(bounds: f32) -> BackgroundConfig {
+    BackgroundConfig {
+        grid: Some(GroundGrid {
+            height: FLOOR_ALTITUDE + FLOOR_HALF_THICKNESS + 0.002,
+            cell_size: 1.0,
+            major_every: 5,
+            fade_distance: bounds * 4.0,
+            ..GroundGrid::default()
+        }),
+        clear_color: wgpu::Color {
+            r: 0.07,
+            g: 0.08,
+            b: 0.11,
+            a: 1.0,
+        },
+        ..BackgroundConfig::default()
+    }
+}
+
 fn floor_instance(bounds: f32) -> RenderInstance {
     RenderInstance::oriented_box(
         [0.0, FLOOR_ALTITUDE - FLOOR_HALF_THICKNESS, 0.0],
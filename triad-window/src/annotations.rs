@@ -0,0 +1,204 @@
+use glam::{Mat4, Vec2, Vec3};
+
+const ANCHOR_RADIUS: f32 = 2.5;
+const LABEL_PADDING: f32 = 3.0;
+const DEFAULT_LEADER: Vec2 = Vec2::new(12.0, -18.0);
+
+/// A text label pinned to a world-space position.
+///
+/// Labels are drawn as screen-aligned egui overlays, offset from the projected anchor by
+/// `leader` (logical pixels, +y down) and joined to it by a leader line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    pub position: Vec3,
+    pub text: String,
+    /// Linear RGBA, matching the debug-line colors.
+    pub color: [f32; 4],
+    pub leader: Vec2,
+}
+
+impl Annotation {
+    pub fn new(position: Vec3, text: impl Into<String>) -> Self {
+        Self {
+            position,
+            text: text.into(),
+            color: [1.0, 1.0, 1.0, 1.0],
+            leader: DEFAULT_LEADER,
+        }
+    }
+
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Screen-space offset of the label from its anchor; `Vec2::ZERO` draws no leader line.
+    pub fn with_leader(mut self, leader: Vec2) -> Self {
+        self.leader = leader;
+        self
+    }
+}
+
+/// Project `position` into a `viewport` of logical pixels (origin top-left).
+///
+/// Returns `None` when the point is behind the camera or outside the depth range.
+pub fn project_to_viewport(view_proj: Mat4, position: Vec3, viewport: Vec2) -> Option<Vec2> {
+    let clip = view_proj * position.extend(1.0);
+    if clip.w <= f32::EPSILON {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    if !(0.0..=1.0).contains(&ndc.z) {
+        return None;
+    }
+    Some(Vec2::new(
+        (ndc.x + 1.0) * 0.5 * viewport.x,
+        (1.0 - ndc.y) * 0.5 * viewport.y,
+    ))
+}
+
+/// Paint `annotations` onto the egui background layer, beneath every window.
+pub(crate) fn paint_annotations(ctx: &egui::Context, view_proj: Mat4, annotations: &[Annotation]) {
+    if annotations.is_empty() {
+        return;
+    }
+    let screen = ctx.content_rect();
+    let viewport = Vec2::new(screen.width(), screen.height());
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    let font = egui::FontId::proportional(13.0);
+
+    for annotation in annotations {
+        let Some(anchor) = project_to_viewport(view_proj, annotation.position, viewport) else {
+            continue;
+        };
+        let anchor = screen.min + egui::vec2(anchor.x, anchor.y);
+        if !screen.contains(anchor) {
+            continue;
+        }
+
+        let [r, g, b, a] = annotation.color;
+        let color = egui::Rgba::from_rgba_unmultiplied(r, g, b, a).into();
+        let label_pos = anchor + egui::vec2(annotation.leader.x, annotation.leader.y);
+
+        painter.circle_filled(anchor, ANCHOR_RADIUS, color);
+        if annotation.leader != Vec2::ZERO {
+            painter.line_segment([anchor, label_pos], egui::Stroke::new(1.0, color));
+        }
+
+        let galley = painter.layout_no_wrap(annotation.text.clone(), font.clone(), color);
+        // Grow the label away from the anchor so the leader line meets its near edge.
+        let align = egui::Align2([
+            if annotation.leader.x < 0.0 {
+                egui::Align::Max
+            } else {
+                egui::Align::Min
+            },
+            if annotation.leader.y < 0.0 {
+                egui::Align::Max
+            } else {
+                egui::Align::Min
+            },
+        ]);
+        let rect = align.anchor_size(label_pos, galley.size());
+        painter.rect_filled(
+            rect.expand(LABEL_PADDING),
+            2.0,
+            egui::Color32::from_black_alpha(160),
+        );
+        painter.galley(rect.min, galley, color);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::{Camera, Projection};
+    use triad_gpu::DepthMode;
+
+    const VIEWPORT: Vec2 = Vec2::new(800.0, 600.0);
+
+    /// Camera at +z looking at the origin with near 0.1 and far 100.
+    fn view_proj(depth_mode: DepthMode, infinite_far: bool) -> Mat4 {
+        let projection = Projection::new(800, 600, 60f32.to_radians(), 0.1, 100.0)
+            .with_depth_mode(depth_mode)
+            .with_infinite_far(infinite_far);
+        let camera = Camera::new(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO);
+        projection.matrix() * camera.view_matrix()
+    }
+
+    fn all_modes() -> impl Iterator<Item = (DepthMode, bool)> {
+        [DepthMode::Standard, DepthMode::Reversed]
+            .into_iter()
+            .flat_map(|mode| [(mode, false), (mode, true)])
+    }
+
+    #[test]
+    fn test_project_center_of_screen() {
+        for (mode, infinite_far) in all_modes() {
+            let point = project_to_viewport(view_proj(mode, infinite_far), Vec3::ZERO, VIEWPORT)
+                .unwrap_or_else(|| panic!("origin rejected for {mode:?}, infinite {infinite_far}"));
+            assert!(
+                (point - VIEWPORT * 0.5).length() < 1e-3,
+                "{mode:?}, infinite {infinite_far}: {point}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_uses_top_left_origin() {
+        let view_proj = view_proj(DepthMode::Standard, false);
+        let point = project_to_viewport(view_proj, Vec3::new(1.0, 1.0, 0.0), VIEWPORT)
+            .expect("point in front of the camera");
+        assert!(point.x > VIEWPORT.x * 0.5);
+        assert!(point.y < VIEWPORT.y * 0.5);
+    }
+
+    #[test]
+    fn test_project_rejects_point_behind_camera() {
+        for (mode, infinite_far) in all_modes() {
+            let behind = Vec3::new(0.0, 0.0, 20.0);
+            assert_eq!(
+                project_to_viewport(view_proj(mode, infinite_far), behind, VIEWPORT),
+                None,
+                "{mode:?}, infinite {infinite_far}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_rejects_point_beyond_far_plane() {
+        let beyond = Vec3::new(0.0, 0.0, -200.0);
+        for mode in [DepthMode::Standard, DepthMode::Reversed] {
+            assert_eq!(
+                project_to_viewport(view_proj(mode, false), beyond, VIEWPORT),
+                None,
+                "{mode:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_infinite_far_keeps_distant_points() {
+        let distant = Vec3::new(0.0, 0.0, -10_000.0);
+        for mode in [DepthMode::Standard, DepthMode::Reversed] {
+            let point = project_to_viewport(view_proj(mode, true), distant, VIEWPORT)
+                .unwrap_or_else(|| panic!("distant point rejected for {mode:?}"));
+            assert!(
+                (point - VIEWPORT * 0.5).length() < 1e-3,
+                "{mode:?}: {point}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_project_rejects_point_nearer_than_near_plane() {
+        let too_near = Vec3::new(0.0, 0.0, 9.95);
+        for (mode, infinite_far) in all_modes() {
+            assert_eq!(
+                project_to_viewport(view_proj(mode, infinite_far), too_near, VIEWPORT),
+                None,
+                "{mode:?}, infinite {infinite_far}"
+            );
+        }
+    }
+}
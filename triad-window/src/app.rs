@@ -1,20 +1,23 @@
-use crate::camera::{Camera, Projection};
+use crate::annotations::{Annotation, paint_annotations};
+use crate::camera::{Camera, Projection, SceneBounds};
 use crate::camera_uniforms::CameraUniforms;
 use crate::controls::Controls;
+use crate::keybindings::{KeyBindings, key_name};
 use glam::Vec3;
 use std::error::Error;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tracing::{debug_span, error, info, instrument};
 use triad_gpu::wgpu;
 use triad_gpu::{
-    ExecutableFrameGraph, FrameGraphError, Renderer, ResourceRegistry, SurfaceWrapper,
+    BackgroundConfig, DepthMode, ExecutableFrameGraph, FrameGraphError, PresentModePreference,
+    Renderer, ResourceRegistry, SurfaceWrapper,
 };
 use winit::application::ApplicationHandler;
-use winit::dpi::PhysicalSize;
+use winit::dpi::{LogicalSize, PhysicalSize};
 use winit::event::{ElementState, KeyEvent, WindowEvent};
-use winit::event_loop::EventLoop;
-use winit::keyboard::{KeyCode, PhysicalKey};
+use winit::event_loop::{ControlFlow, EventLoop};
+use winit::keyboard::PhysicalKey;
 use winit::window::{Window, WindowId};
 
 pub use egui;
@@ -31,19 +34,106 @@ pub enum RenderError {
     RendererManager(String),
 }
 
-#[derive(Debug, Clone, Copy)]
-pub struct WindowConfig {
+/// Surface presentation and frame pacing options, applied when the render
+/// surface is created.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RendererConfig {
     pub present_mode: wgpu::PresentMode,
+    /// Frames the presentation engine may queue ahead (`desired_maximum_frame_latency`).
+    pub max_frame_latency: u32,
+    /// Cap the frame rate by sleeping between frames; `None` runs uncapped.
+    pub target_fps: Option<f32>,
+    /// Sky and ground grid behind the scene, handed to the renderer manager.
+    pub background: BackgroundConfig,
 }
 
-impl Default for WindowConfig {
+impl Default for RendererConfig {
     fn default() -> Self {
         Self {
             present_mode: wgpu::PresentMode::AutoVsync,
+            max_frame_latency: triad_gpu::DEFAULT_MAX_FRAME_LATENCY,
+            target_fps: None,
+            background: BackgroundConfig::default(),
         }
     }
 }
 
+impl RendererConfig {
+    /// Toggle vsync using the automatic present modes, which always resolve
+    /// to something the surface supports.
+    pub fn with_vsync(mut self, vsync: bool) -> Self {
+        self.present_mode = if vsync {
+            wgpu::PresentMode::AutoVsync
+        } else {
+            wgpu::PresentMode::AutoNoVsync
+        };
+        self
+    }
+
+    pub fn with_present_mode(mut self, present_mode: wgpu::PresentMode) -> Self {
+        self.present_mode = present_mode;
+        self
+    }
+
+    pub fn with_max_frame_latency(mut self, max_frame_latency: u32) -> Self {
+        self.max_frame_latency = max_frame_latency;
+        self
+    }
+
+    pub fn with_target_fps(mut self, target_fps: Option<f32>) -> Self {
+        self.target_fps = target_fps;
+        self
+    }
+
+    pub fn with_background(mut self, background: BackgroundConfig) -> Self {
+        self.background = background;
+        self
+    }
+}
+
+/// Window configuration for [`run_with_renderer_config`].
+#[derive(Debug, Clone, Default)]
+pub struct WindowConfig {
+    /// Presentation and frame pacing for the render surface.
+    pub renderer: RendererConfig,
+    /// Window-level hotkeys (quit, UI toggle, scene framing, bindings overlay).
+    pub key_bindings: KeyBindings,
+    /// Depth convention of the camera projection; renderer pipelines must match it.
+    pub depth_mode: DepthMode,
+    /// Use a projection with no far plane.
+    pub infinite_far: bool,
+}
+
+impl WindowConfig {
+    pub fn with_renderer_config(mut self, renderer: RendererConfig) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    pub fn with_key_bindings(mut self, key_bindings: KeyBindings) -> Self {
+        self.key_bindings = key_bindings;
+        self
+    }
+
+    /// Render with reverse-Z depth; see [`DepthMode::Reversed`].
+    pub fn with_depth_mode(mut self, depth_mode: DepthMode) -> Self {
+        self.depth_mode = depth_mode;
+        self
+    }
+
+    pub fn with_infinite_far(mut self, infinite_far: bool) -> Self {
+        self.infinite_far = infinite_far;
+        self
+    }
+}
+
+/// Minimum time between frames for a target FPS cap.
+fn frame_interval(target_fps: Option<f32>) -> Option<Duration> {
+    target_fps
+        .filter(|fps| fps.is_finite() && *fps > 0.0)
+        .map(|fps| Duration::from_secs_f32(1.0 / fps))
+}
+
 pub fn run_with_renderer_config<F, M>(
     title: &str,
     config: WindowConfig,
@@ -62,7 +152,13 @@ where
         + Send
         + 'static,
 {
-    info!(title, ?config.present_mode, "creating event loop");
+    info!(
+        title,
+        ?config.renderer.present_mode,
+        config.renderer.max_frame_latency,
+        ?config.renderer.target_fps,
+        "creating event loop"
+    );
     let event_loop = EventLoop::new().map_err(|e| format!("Failed to create event loop: {e}"))?;
     let mut controls = Controls::default();
     configure_controls(&mut controls);
@@ -76,22 +172,23 @@ where
     app_result
 }
 
+/// Deferred constructor for the embedder's renderer manager.
+type CreateManagerFn = Box<
+    dyn FnOnce(
+            &Renderer,
+            &mut ResourceRegistry,
+            wgpu::TextureFormat,
+            u32,
+            u32,
+        ) -> Result<Box<dyn RendererManager>, Box<dyn Error>>
+        + Send,
+>;
+
 struct App {
     title: String,
     config: Option<WindowConfig>,
     controls: Option<Controls>,
-    create_manager: Option<
-        Box<
-            dyn FnOnce(
-                    &Renderer,
-                    &mut ResourceRegistry,
-                    wgpu::TextureFormat,
-                    u32,
-                    u32,
-                ) -> Result<Box<dyn RendererManager>, Box<dyn Error>>
-                + Send,
-        >,
-    >,
+    create_manager: Option<CreateManagerFn>,
     state: Option<ViewerState>,
     error: Option<String>,
 }
@@ -177,7 +274,18 @@ impl ApplicationHandler for App {
         match event {
             WindowEvent::CloseRequested => event_loop.exit(),
             WindowEvent::Resized(size) => state.resize(size),
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                info!(scale_factor, "window scale factor changed");
+                // Some platforms change the physical size without a Resized event.
+                let size = state.window.inner_size();
+                state.resize(size);
+            }
             WindowEvent::RedrawRequested => {
+                let size = state.window.inner_size();
+                if size.width == 0 || size.height == 0 {
+                    // Minimized: nothing to present until the window is restored.
+                    return;
+                }
                 let _frame_span = tracing::info_span!("frame").entered();
                 match state.render() {
                     Ok(()) => {}
@@ -185,9 +293,12 @@ impl ApplicationHandler for App {
                         RenderError::Surface(triad_gpu::wgpu::SurfaceError::Lost)
                         | RenderError::Surface(triad_gpu::wgpu::SurfaceError::Outdated),
                     ) => {
-                        let size = state.window.inner_size();
+                        // Reconfiguring in-frame did not help; rebuild everything next frame.
                         state.resize(size);
                     }
+                    Err(RenderError::Surface(triad_gpu::wgpu::SurfaceError::Timeout)) => {
+                        tracing::debug!("surface acquire timed out; skipping frame");
+                    }
                     Err(RenderError::Surface(triad_gpu::wgpu::SurfaceError::OutOfMemory)) => {
                         error!("GPU Out of Memory - exiting");
                         event_loop.exit();
@@ -199,10 +310,19 @@ impl ApplicationHandler for App {
         }
     }
 
-    fn about_to_wait(&mut self, _event_loop: &winit::event_loop::ActiveEventLoop) {
-        if let Some(state) = self.state.as_ref() {
-            state.window.request_redraw();
+    fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        let Some(state) = self.state.as_ref() else {
+            return;
+        };
+        if let Some(interval) = state.frame_interval {
+            let next_frame = state.last_frame + interval;
+            if Instant::now() < next_frame {
+                event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame));
+                return;
+            }
         }
+        event_loop.set_control_flow(ControlFlow::Wait);
+        state.window.request_redraw();
     }
 }
 
@@ -230,7 +350,11 @@ struct ViewerState {
     current_present_mode: wgpu::PresentMode,
     pending_present_mode: Option<wgpu::PresentMode>,
     pending_resize: Option<PhysicalSize<u32>>,
+    frame_interval: Option<Duration>,
     show_ui: bool,
+    show_annotations: bool,
+    key_bindings: KeyBindings,
+    show_keybindings: bool,
 }
 
 pub trait RendererManager: Send + Sync {
@@ -257,6 +381,30 @@ pub trait RendererManager: Send + Sync {
         width: u32,
         height: u32,
     ) -> Result<(), Box<dyn Error>>;
+
+    /// World-space bounds of the current scene, recomputed on each call.
+    /// Used to frame the camera (Home key); `None` disables framing.
+    fn scene_bounds(&self) -> Option<SceneBounds> {
+        None
+    }
+
+    /// World-space labels drawn over the scene each frame.
+    fn annotations(&self) -> &[Annotation] {
+        &[]
+    }
+
+    /// Multiplier on drawn point and splat sizes; 1 is the size set at init.
+    fn point_scale(&self) -> f32 {
+        1.0
+    }
+
+    /// Change the drawn point size without rebuilding buffers.
+    /// Managers without scalable points ignore this.
+    fn set_point_scale(&mut self, _scale: f32) {}
+
+    /// Apply [`RendererConfig::background`]; called once after the manager is created.
+    /// Managers without a background pass ignore this.
+    fn set_background(&mut self, _background: BackgroundConfig) {}
 }
 
 impl ViewerState {
@@ -264,24 +412,20 @@ impl ViewerState {
         event_loop: &winit::event_loop::ActiveEventLoop,
         title: &str,
         config: WindowConfig,
-        controls: Controls,
-        create_manager: Box<
-            dyn FnOnce(
-                    &Renderer,
-                    &mut ResourceRegistry,
-                    triad_gpu::wgpu::TextureFormat,
-                    u32,
-                    u32,
-                ) -> Result<Box<dyn RendererManager>, Box<dyn Error>>
-                + Send,
-        >,
+        mut controls: Controls,
+        create_manager: CreateManagerFn,
     ) -> Result<Self, Box<dyn Error>> {
         info!(title, "creating native window");
         let window_attributes = Window::default_attributes()
             .with_title(title)
-            .with_inner_size(PhysicalSize::new(1280, 720));
+            .with_inner_size(LogicalSize::new(1280, 720));
         let window = Arc::new(event_loop.create_window(window_attributes)?);
-        info!(window_id = ?window.id(), "native window created");
+        info!(
+            window_id = ?window.id(),
+            scale_factor = window.scale_factor(),
+            "native window created"
+        );
+        controls.set_scale_factor(window.scale_factor());
 
         info!("requesting renderer");
         let renderer = pollster::block_on(Renderer::new())?;
@@ -295,13 +439,20 @@ impl ViewerState {
 
         info!("creating render surface");
         let surface = renderer.instance().create_surface(window.clone())?;
-        let surface = renderer.create_surface_with_mode(
+        let surface = renderer.create_surface_with_latency(
             surface,
             size.width.max(1),
             size.height.max(1),
-            config.present_mode,
+            config.renderer.present_mode,
+            config.renderer.max_frame_latency,
         )?;
-        info!(format = ?surface.format(), ?config.present_mode, "surface configured");
+        info!(
+            format = ?surface.format(),
+            requested = ?config.renderer.present_mode,
+            actual = ?surface.present_mode(),
+            "surface configured"
+        );
+        let current_present_mode = surface.present_mode();
 
         let mut registry = ResourceRegistry::default();
         let camera = Camera::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO);
@@ -311,7 +462,9 @@ impl ViewerState {
             std::f32::consts::FRAC_PI_3,
             0.01,
             10000.0,
-        );
+        )
+        .with_depth_mode(config.depth_mode)
+        .with_infinite_far(config.infinite_far);
 
         let (depth_texture, depth_view) = Self::create_depth_texture(
             renderer.device(),
@@ -336,13 +489,14 @@ impl ViewerState {
         );
 
         info!("creating renderer manager");
-        let renderer_manager = create_manager(
+        let mut renderer_manager = create_manager(
             &renderer,
             &mut registry,
             surface.format(),
             size.width.max(1),
             size.height.max(1),
         )?;
+        renderer_manager.set_background(config.renderer.background);
         info!("renderer manager created");
 
         Ok(Self {
@@ -366,10 +520,14 @@ impl ViewerState {
             fps_display: 60.0,
             frame_graph_rebuilt_last_frame: true,
             frame_graph_command_buffers_last_frame: 0,
-            current_present_mode: config.present_mode,
+            current_present_mode,
             pending_present_mode: None,
             pending_resize: None,
+            frame_interval: frame_interval(config.renderer.target_fps),
             show_ui: true,
+            show_annotations: true,
+            key_bindings: config.key_bindings,
+            show_keybindings: false,
         })
     }
 
@@ -418,23 +576,46 @@ impl ViewerState {
             ..
         } = event
         {
-            match key_code {
-                KeyCode::Escape => {
-                    event_loop.exit();
-                    return true;
-                }
-                KeyCode::F1 => {
-                    self.show_ui = !self.show_ui;
-                    tracing::info!("UI visibility: {}", self.show_ui);
-                    return true;
-                }
-                _ => {}
+            let bindings = &self.key_bindings;
+            if bindings.is_bound(KeyBindings::QUIT, *key_code) {
+                event_loop.exit();
+                return true;
+            }
+            if bindings.is_bound(KeyBindings::TOGGLE_UI, *key_code) {
+                self.show_ui = !self.show_ui;
+                tracing::info!("UI visibility: {}", self.show_ui);
+                return true;
+            }
+            if bindings.is_bound(KeyBindings::SHOW_KEYBINDINGS, *key_code) {
+                self.show_keybindings = !self.show_keybindings;
+                return true;
+            }
+            if bindings.is_bound(KeyBindings::FRAME_SCENE, *key_code) {
+                self.frame_scene();
+                return true;
             }
         }
 
         self.controls.handle_event(event)
     }
 
+    /// Move the camera so the renderer manager's scene bounds fill the view.
+    fn frame_scene(&mut self) {
+        let Some(bounds) = self.renderer_manager.scene_bounds() else {
+            tracing::debug!("frame scene: renderer manager reports no bounds");
+            return;
+        };
+        let pose =
+            self.camera
+                .pose()
+                .framing(&bounds, self.projection.fov(), self.projection.aspect());
+        let needed_far = (pose.position - bounds.center()).length() + bounds.radius() * 2.0;
+        if self.projection.far() < needed_far {
+            self.projection.set_far(needed_far);
+        }
+        self.controls.request_reset(pose);
+    }
+
     fn resize(&mut self, new_size: PhysicalSize<u32>) {
         if new_size.width == 0 || new_size.height == 0 {
             return;
@@ -443,10 +624,12 @@ impl ViewerState {
     }
 
     fn apply_resize(&mut self, new_size: PhysicalSize<u32>) {
-        let mut config = self.surface.config().clone();
-        config.width = new_size.width;
-        config.height = new_size.height;
-        self.surface.reconfigure(self.renderer.device(), config);
+        if !self
+            .surface
+            .resize(self.renderer.device(), new_size.width, new_size.height)
+        {
+            return;
+        }
         self.projection.update_size(new_size.width, new_size.height);
 
         if let Err(e) = self.renderer_manager.resize(
@@ -486,25 +669,43 @@ impl ViewerState {
             self.current_present_mode,
             present_mode
         );
-        self.current_present_mode = present_mode;
-        let mut config = self.surface.config().clone();
-        config.present_mode = present_mode;
-        self.surface.reconfigure(self.renderer.device(), config);
-
-        let actual_mode = self.surface.config().present_mode;
+        let actual_mode = self
+            .surface
+            .set_present_mode(self.renderer.device(), present_mode);
+        self.current_present_mode = actual_mode;
         tracing::info!(
             "Surface reconfigured - actual present mode: {:?}",
             actual_mode
         );
         if actual_mode != present_mode {
             tracing::warn!(
-                "Present mode mismatch! Requested {:?} but got {:?}",
+                "Present mode {:?} unsupported; fell back to {:?}",
                 present_mode,
                 actual_mode
             );
         }
     }
 
+    /// Acquire the next swapchain image, recreating the swapchain once if it is
+    /// lost or outdated (e.g. after a display change or a missed resize event).
+    fn acquire_surface_texture(
+        &mut self,
+    ) -> Result<triad_gpu::wgpu::SurfaceTexture, triad_gpu::wgpu::SurfaceError> {
+        match self.surface.get_current_texture() {
+            Err(err @ (wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated)) => {
+                tracing::debug!("surface {err}; reconfiguring swapchain");
+                let size = self.window.inner_size();
+                if size.width != self.surface.width() || size.height != self.surface.height() {
+                    self.apply_resize(size);
+                } else {
+                    self.surface.configure(self.renderer.device());
+                }
+                self.surface.get_current_texture()
+            }
+            result => result,
+        }
+    }
+
     #[instrument(skip(self), name = "render")]
     fn render(&mut self) -> Result<(), RenderError> {
         if let Some(present_mode) = self.pending_present_mode.take() {
@@ -538,7 +739,7 @@ impl ViewerState {
 
         let (surface_texture, surface_view) = {
             let _span = debug_span!("surface_acquire").entered();
-            let surface_texture = self.surface.get_current_texture()?;
+            let surface_texture = self.acquire_surface_texture()?;
             let surface_view = Arc::new(
                 surface_texture
                     .texture
@@ -581,15 +782,45 @@ impl ViewerState {
         self.frame_graph_rebuilt_last_frame = rebuilt_frame_graph;
         self.frame_graph_command_buffers_last_frame = command_buffers.len();
 
+        let view_proj = self.projection.matrix() * self.camera.view_matrix();
         let (full_output, new_present_mode) = if self.show_ui {
             let _span = debug_span!("egui_run").entered();
             let raw_input = self.egui_winit.take_egui_input(&self.window);
             let mut new_mode = None;
+            let mut new_frame_interval = None;
+            let mut frame_requested = false;
             let output = self.egui_ctx.run(raw_input, |ctx| {
                 ctx.request_repaint_after(std::time::Duration::from_millis(100));
 
+                if self.show_annotations {
+                    paint_annotations(ctx, view_proj, self.renderer_manager.annotations());
+                }
+
                 self.controls.run_ui(ctx);
 
+                if self.show_keybindings {
+                    egui::Window::new("Key bindings")
+                        .default_pos(egui::pos2(10.0, 220.0))
+                        .resizable(false)
+                        .show(ctx, |ui| {
+                            egui::Grid::new("key_bindings")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for (action, keys) in self.key_bindings.iter() {
+                                        ui.label(action);
+                                        let names: Vec<String> =
+                                            keys.iter().copied().map(key_name).collect();
+                                        ui.label(if names.is_empty() {
+                                            "unbound".to_string()
+                                        } else {
+                                            names.join(", ")
+                                        });
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                }
+
                 egui::Window::new("Performance")
                     .default_pos(egui::pos2(10.0, 10.0))
                     .resizable(false)
@@ -638,58 +869,59 @@ impl ViewerState {
                         ui.horizontal(|ui| {
                             ui.label("Present:");
 
-                            if ui
-                                .selectable_label(
-                                    matches!(
-                                        self.current_present_mode,
-                                        wgpu::PresentMode::AutoVsync
-                                    ),
-                                    "Auto vsync",
-                                )
-                                .clicked()
-                            {
-                                new_mode = Some(wgpu::PresentMode::AutoVsync);
+                            let supported = self.surface.supported_present_modes();
+                            for (preference, label) in [
+                                (PresentModePreference::Vsync, "Vsync"),
+                                (PresentModePreference::Mailbox, "Mailbox"),
+                                (PresentModePreference::Immediate, "Immediate"),
+                            ] {
+                                let mode = preference.resolve(supported);
+                                if ui
+                                    .selectable_label(self.current_present_mode == mode, label)
+                                    .on_hover_text(format!("{mode:?}"))
+                                    .clicked()
+                                {
+                                    new_mode = Some(mode);
+                                }
                             }
+                        });
 
-                            if ui
-                                .selectable_label(
-                                    matches!(self.current_present_mode, wgpu::PresentMode::Fifo),
-                                    "FIFO",
-                                )
-                                .clicked()
+                        ui.horizontal(|ui| {
+                            ui.label("Cap:");
+                            for (target_fps, label) in
+                                [(None, "Off"), (Some(30.0), "30"), (Some(60.0), "60")]
                             {
-                                new_mode = Some(wgpu::PresentMode::Fifo);
+                                let interval = frame_interval(target_fps);
+                                if ui
+                                    .selectable_label(self.frame_interval == interval, label)
+                                    .clicked()
+                                {
+                                    new_frame_interval = Some(interval);
+                                }
                             }
+                        });
 
-                            if ui
-                                .selectable_label(
-                                    matches!(self.current_present_mode, wgpu::PresentMode::Mailbox),
-                                    "Mailbox",
-                                )
-                                .clicked()
-                            {
-                                new_mode = Some(wgpu::PresentMode::Mailbox);
-                            }
+                        ui.checkbox(&mut self.show_annotations, "Labels");
 
-                            if ui
-                                .selectable_label(
-                                    matches!(
-                                        self.current_present_mode,
-                                        wgpu::PresentMode::AutoNoVsync
-                                    ),
-                                    "Auto no vsync",
-                                )
-                                .clicked()
-                            {
-                                new_mode = Some(wgpu::PresentMode::AutoNoVsync);
-                            }
-                        });
+                        if ui.button("Frame scene").on_hover_text("Home").clicked() {
+                            frame_requested = true;
+                        }
                     });
             });
+            if let Some(interval) = new_frame_interval {
+                self.frame_interval = interval;
+            }
+            if frame_requested {
+                self.frame_scene();
+            }
             (output, Some(new_mode))
         } else {
             let raw_input = self.egui_winit.take_egui_input(&self.window);
-            let output = self.egui_ctx.run(raw_input, |_ctx| {});
+            let output = self.egui_ctx.run(raw_input, |ctx| {
+                if self.show_annotations {
+                    paint_annotations(ctx, view_proj, self.renderer_manager.annotations());
+                }
+            });
             (output, None)
         };
 
@@ -1,4 +1,50 @@
 use glam::{Mat4, Vec2, Vec3};
+use triad_gpu::DepthMode;
+
+/// Axis-aligned world-space bounds of a scene's content.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SceneBounds {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl SceneBounds {
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self {
+            min: min.min(max),
+            max: min.max(max),
+        }
+    }
+
+    /// Smallest bounds containing every point, or `None` for an empty iterator.
+    pub fn from_points(points: impl IntoIterator<Item = Vec3>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(Self::new(first, first), |bounds, p| {
+            bounds.union(&Self::new(p, p))
+        }))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self {
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) * 0.5
+    }
+
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    /// Radius of the bounding sphere around [`center`](Self::center).
+    pub fn radius(&self) -> f32 {
+        self.size().length() * 0.5
+    }
+}
 
 /// Camera pose representing position and orientation.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -58,11 +104,25 @@ impl CameraPose {
         self.position += pan;
     }
 
+    /// Pose that fits `bounds` in view, keeping the current viewing direction.
+    /// `fov_y` is the vertical field of view in radians; `aspect` is width / height.
+    pub fn framing(&self, bounds: &SceneBounds, fov_y: f32, aspect: f32) -> CameraPose {
+        let half_fov_y = fov_y * 0.5;
+        let half_fov_x = (half_fov_y.tan() * aspect).atan();
+        let half_fov = half_fov_y.min(half_fov_x).max(0.01);
+        let distance = (bounds.radius() / half_fov.sin()).max(0.1);
+        let direction = (self.position - self.center)
+            .try_normalize()
+            .unwrap_or(Vec3::Z);
+        let center = bounds.center();
+        CameraPose::new(center + direction * distance, center)
+    }
+
     /// Zoom in/out by changing distance to center.
     pub fn zoom(&mut self, amount: f32) {
         let direction = (self.position - self.center).normalize_or_zero();
         let distance = (self.position - self.center).length();
-        let new_distance = (distance + amount).max(0.1).min(1000.0);
+        let new_distance = (distance + amount).clamp(0.1, 1000.0);
         self.position = self.center + direction * new_distance;
     }
 }
@@ -111,6 +171,8 @@ pub struct Projection {
     fov: f32,
     near: f32,
     far: f32,
+    depth_mode: DepthMode,
+    infinite_far: bool,
 }
 
 impl Projection {
@@ -122,17 +184,44 @@ impl Projection {
             fov,
             near,
             far,
+            depth_mode: DepthMode::Standard,
+            infinite_far: false,
         }
     }
 
+    /// Map depth in `depth_mode`; pipelines and depth clears must use the same mode.
+    pub fn with_depth_mode(mut self, depth_mode: DepthMode) -> Self {
+        self.depth_mode = depth_mode;
+        self
+    }
+
+    /// Push the far plane to infinity; `far()` is then only used for framing.
+    pub fn with_infinite_far(mut self, infinite_far: bool) -> Self {
+        self.infinite_far = infinite_far;
+        self
+    }
+
     /// Get the projection matrix.
     pub fn matrix(&self) -> Mat4 {
-        Mat4::perspective_rh(
-            self.fov,
-            self.width as f32 / self.height as f32,
-            self.near,
-            self.far,
-        )
+        let aspect = self.width as f32 / self.height as f32;
+        match (self.depth_mode, self.infinite_far) {
+            (DepthMode::Standard, false) => {
+                Mat4::perspective_rh(self.fov, aspect, self.near, self.far)
+            }
+            (DepthMode::Standard, true) => {
+                Mat4::perspective_infinite_rh(self.fov, aspect, self.near)
+            }
+            (DepthMode::Reversed, false) => {
+                Mat4::perspective_rh(self.fov, aspect, self.far, self.near)
+            }
+            (DepthMode::Reversed, true) => {
+                Mat4::perspective_infinite_reverse_rh(self.fov, aspect, self.near)
+            }
+        }
+    }
+
+    pub fn depth_mode(&self) -> DepthMode {
+        self.depth_mode
     }
 
     /// Update the projection size.
@@ -151,6 +240,16 @@ impl Projection {
         self.far = far;
     }
 
+    /// Vertical field of view in radians.
+    pub fn fov(&self) -> f32 {
+        self.fov
+    }
+
+    /// Width / height of the viewport.
+    pub fn aspect(&self) -> f32 {
+        self.width as f32 / self.height.max(1) as f32
+    }
+
     /// Get the far plane distance.
     pub fn far(&self) -> f32 {
         self.far
@@ -162,3 +261,130 @@ pub trait CameraController {
     fn update() {}
     fn setup() {}
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn corners(bounds: &SceneBounds) -> impl Iterator<Item = Vec3> + '_ {
+        (0..8).map(|i| {
+            Vec3::select(
+                glam::BVec3::new(i & 1 != 0, i & 2 != 0, i & 4 != 0),
+                bounds.max,
+                bounds.min,
+            )
+        })
+    }
+
+    fn assert_framed(bounds: &SceneBounds, start: CameraPose, width: u32, height: u32) {
+        let projection = Projection::new(width, height, 60f32.to_radians(), 0.01, 1000.0);
+        let pose = start.framing(bounds, projection.fov(), projection.aspect());
+        let mut camera = Camera::new(Vec3::Z, Vec3::ZERO);
+        camera.apply_pose(&pose);
+        let view_proj = projection.matrix() * camera.view_matrix();
+
+        for corner in corners(bounds) {
+            let clip = view_proj * corner.extend(1.0);
+            assert!(clip.w > 0.0, "{corner} is behind the camera");
+            let ndc = clip / clip.w;
+            assert!(
+                ndc.x.abs() <= 1.0 && ndc.y.abs() <= 1.0 && (0.0..=1.0).contains(&ndc.z),
+                "{corner} projects outside the frustum: {ndc}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_framing_keeps_all_corners_in_frustum() {
+        let bounds = SceneBounds::new(Vec3::new(-3.0, -1.0, -2.0), Vec3::new(5.0, 2.0, 4.0));
+        let start = CameraPose::new(Vec3::new(4.0, 3.0, 10.0), Vec3::ZERO);
+        assert_framed(&bounds, start, 1280, 720);
+    }
+
+    #[test]
+    fn test_framing_narrow_aspect_uses_horizontal_fov() {
+        let bounds = SceneBounds::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+        let start = CameraPose::new(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO);
+        assert_framed(&bounds, start, 400, 1000);
+
+        let fov_y = 60f32.to_radians();
+        let wide = start.framing(&bounds, fov_y, 1.0);
+        let narrow = start.framing(&bounds, fov_y, 0.4);
+        let distance = |pose: CameraPose| (pose.position - pose.center).length();
+        assert!(distance(narrow) > distance(wide));
+    }
+
+    #[test]
+    fn test_framing_single_point_bounds() {
+        let point = Vec3::new(2.0, -1.0, 3.0);
+        let bounds = SceneBounds::from_points([point]).expect("bounds");
+        assert_eq!(bounds.radius(), 0.0);
+
+        let start = CameraPose::new(Vec3::new(0.0, 0.0, 10.0), Vec3::ZERO);
+        let pose = start.framing(&bounds, 60f32.to_radians(), 16.0 / 9.0);
+        assert_eq!(pose.center, point);
+        assert!(pose.position.is_finite());
+        assert!(((pose.position - point).length() - 0.1).abs() < 1e-5);
+        assert_framed(&bounds, start, 1280, 720);
+    }
+
+    #[test]
+    fn test_framing_keeps_view_direction() {
+        let bounds = SceneBounds::new(Vec3::splat(-1.0), Vec3::splat(1.0));
+        let start = CameraPose::new(Vec3::new(3.0, 4.0, 0.0), Vec3::ZERO);
+        let pose = start.framing(&bounds, 60f32.to_radians(), 1.0);
+        let direction = (pose.position - pose.center).normalize();
+        assert!((direction - Vec3::new(0.6, 0.8, 0.0)).length() < 1e-5);
+    }
+
+    /// NDC depth of a point `distance` in front of the camera.
+    fn depth_at(projection: &Projection, distance: f32) -> f32 {
+        let clip = projection.matrix() * Vec3::new(0.0, 0.0, -distance).extend(1.0);
+        clip.z / clip.w
+    }
+
+    #[test]
+    fn test_projection_depth_in_each_mode() {
+        let (near, far) = (0.1, 100.0);
+        let projection = |depth_mode, infinite_far| {
+            Projection::new(800, 600, 60f32.to_radians(), near, far)
+                .with_depth_mode(depth_mode)
+                .with_infinite_far(infinite_far)
+        };
+
+        let standard = projection(DepthMode::Standard, false);
+        assert!(depth_at(&standard, near).abs() < 1e-5);
+        assert!((depth_at(&standard, far) - 1.0).abs() < 1e-5);
+
+        let reversed = projection(DepthMode::Reversed, false);
+        assert!((depth_at(&reversed, near) - 1.0).abs() < 1e-5);
+        assert!(depth_at(&reversed, far).abs() < 1e-5);
+
+        let standard_infinite = projection(DepthMode::Standard, true);
+        assert!(depth_at(&standard_infinite, near).abs() < 1e-5);
+        let distant = depth_at(&standard_infinite, 1.0e6);
+        assert!(distant.is_finite() && distant < 1.0 && distant > 0.999);
+
+        let reversed_infinite = projection(DepthMode::Reversed, true);
+        assert!((depth_at(&reversed_infinite, near) - 1.0).abs() < 1e-5);
+        let distant = depth_at(&reversed_infinite, 1.0e6);
+        assert!(distant.is_finite() && distant > 0.0 && distant < 1.0e-3);
+    }
+
+    #[test]
+    fn test_projection_depth_is_monotonic() {
+        for depth_mode in [DepthMode::Standard, DepthMode::Reversed] {
+            for infinite_far in [false, true] {
+                let projection = Projection::new(800, 600, 60f32.to_radians(), 0.1, 100.0)
+                    .with_depth_mode(depth_mode)
+                    .with_infinite_far(infinite_far);
+                let near = depth_at(&projection, 1.0);
+                let far = depth_at(&projection, 50.0);
+                match depth_mode {
+                    DepthMode::Standard => assert!(far > near),
+                    DepthMode::Reversed => assert!(far < near),
+                }
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
 
-use glam::Vec2;
-use winit::event::{ElementState, MouseButton, MouseScrollDelta, WindowEvent};
+use glam::{Quat, Vec2, Vec3};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, TouchPhase, WindowEvent};
 use winit::keyboard::PhysicalKey;
 
 use crate::camera::{Camera, CameraPose};
@@ -21,7 +22,10 @@ pub struct CameraIntent {
 }
 
 /// Per-frame snapshot of input state that controllers and hooks can inspect.
-#[derive(Debug, Default)]
+///
+/// Positions and deltas are in physical pixels, matching the surface and viewport.
+/// Use [`InputState::to_logical`] for distances that should feel the same on any DPI.
+#[derive(Debug)]
 pub struct InputState {
     mouse_position: Option<Vec2>,
     mouse_delta: Vec2,
@@ -30,6 +34,26 @@ pub struct InputState {
     keys_pressed: HashSet<PhysicalKey>,
     keys_released: HashSet<PhysicalKey>,
     mouse_down: HashSet<MouseButton>,
+    touches: BTreeMap<u64, Vec2>,
+    pinch_delta: f32,
+    scale_factor: f32,
+}
+
+impl Default for InputState {
+    fn default() -> Self {
+        Self {
+            mouse_position: None,
+            mouse_delta: Vec2::ZERO,
+            scroll_delta: 0.0,
+            keys_down: HashSet::new(),
+            keys_pressed: HashSet::new(),
+            keys_released: HashSet::new(),
+            mouse_down: HashSet::new(),
+            touches: BTreeMap::new(),
+            pinch_delta: 0.0,
+            scale_factor: 1.0,
+        }
+    }
 }
 
 impl InputState {
@@ -61,6 +85,26 @@ impl InputState {
         self.mouse_down.contains(&button)
     }
 
+    /// Active touch points by finger id, in physical pixels.
+    pub fn touches(&self) -> &BTreeMap<u64, Vec2> {
+        &self.touches
+    }
+
+    /// Accumulated trackpad pinch (magnify) this frame; positive means zoom in.
+    pub fn pinch_delta(&self) -> f32 {
+        self.pinch_delta
+    }
+
+    /// Physical pixels per logical pixel for the window, updated live on DPI changes.
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Convert a physical-pixel position or delta to logical pixels.
+    pub fn to_logical(&self, physical: Vec2) -> Vec2 {
+        physical / self.scale_factor
+    }
+
     pub fn is_ctrl_pressed(&self) -> bool {
         self.key_down(PhysicalKey::Code(winit::keyboard::KeyCode::ControlLeft))
             || self.key_down(PhysicalKey::Code(winit::keyboard::KeyCode::ControlRight))
@@ -74,6 +118,7 @@ impl InputState {
     fn end_frame(&mut self) {
         self.mouse_delta = Vec2::ZERO;
         self.scroll_delta = 0.0;
+        self.pinch_delta = 0.0;
         self.keys_pressed.clear();
         self.keys_released.clear();
     }
@@ -104,18 +149,35 @@ impl InputState {
                     }
                 };
             }
+            WindowEvent::Touch(touch) => {
+                let location = Vec2::new(touch.location.x as f32, touch.location.y as f32);
+                match touch.phase {
+                    TouchPhase::Started | TouchPhase::Moved => {
+                        self.touches.insert(touch.id, location);
+                    }
+                    TouchPhase::Ended | TouchPhase::Cancelled => {
+                        self.touches.remove(&touch.id);
+                    }
+                }
+            }
+            WindowEvent::PinchGesture { delta, .. } => {
+                self.pinch_delta += *delta as f32;
+            }
+            WindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+                self.scale_factor = *scale_factor as f32;
+            }
             WindowEvent::KeyboardInput { event, .. } => {
                 let key = event.physical_key;
                 match event.state {
                     ElementState::Pressed => {
-                        if self.keys_down.insert(key.clone()) {
-                            self.keys_pressed.insert(key.clone());
+                        if self.keys_down.insert(key) {
+                            self.keys_pressed.insert(key);
                         }
                         self.keys_released.remove(&key);
                     }
                     ElementState::Released => {
                         self.keys_down.remove(&key);
-                        self.keys_released.insert(key.clone());
+                        self.keys_released.insert(key);
                         self.keys_pressed.remove(&key);
                     }
                 }
@@ -159,20 +221,26 @@ pub struct FrameUpdate<'a> {
     pub reset_request: &'a mut Option<CameraPose>,
 }
 
+type FrameHook = Box<dyn FnMut(FrameUpdate<'_>) + Send>;
+type UiHook = Box<dyn FnMut(&egui::Context) + Send>;
+
 /// Collection of controls plus frame hooks. Acts as the user-facing builder.
 pub struct Controls {
     input: InputState,
     controllers: Vec<ControllerEntry>,
-    frame_hooks: Vec<Box<dyn FnMut(FrameUpdate<'_>) + Send>>,
-    ui_hooks: Vec<Box<dyn FnMut(&egui::Context) + Send>>,
+    frame_hooks: Vec<FrameHook>,
+    ui_hooks: Vec<UiHook>,
     reset: Option<CameraPose>,
     single_active: bool,
 }
 
 impl Controls {
+    /// Mouse and touch controllers. Touch sits below mouse so that
+    /// [`Controls::single_active`] keeps the mouse on desktop.
     pub fn new() -> Self {
         let mut controls = Self::empty();
         controls.add_mouse_controller(MouseController::default(), 0);
+        controls.add_touch_controller(TouchController::default(), -1);
         controls
     }
 
@@ -207,6 +275,14 @@ impl Controls {
         self.add_controller_with_priority(Box::new(controller), priority)
     }
 
+    pub fn add_touch_controller(
+        &mut self,
+        controller: TouchController,
+        priority: i32,
+    ) -> &mut Self {
+        self.add_controller_with_priority(Box::new(controller), priority)
+    }
+
     pub fn add_controller_with_priority(
         &mut self,
         controller: Box<dyn CameraControl>,
@@ -216,7 +292,7 @@ impl Controls {
             priority,
             controller,
         });
-        self.controllers.sort_by(|a, b| a.priority.cmp(&b.priority));
+        self.controllers.sort_by_key(|a| a.priority);
         self
     }
 
@@ -315,6 +391,11 @@ impl Controls {
     pub fn input(&self) -> &InputState {
         &self.input
     }
+
+    /// Seed the window's scale factor; later changes arrive as window events.
+    pub(crate) fn set_scale_factor(&mut self, scale_factor: f64) {
+        self.input.scale_factor = scale_factor as f32;
+    }
 }
 
 impl Default for Controls {
@@ -435,11 +516,12 @@ impl CameraControl for MouseController {
                     let start = input.mouse_position();
                     self.drag_state = Some(DragState::new(mode, start));
                     return true;
-                } else if *state == ElementState::Released && *button == self.drag_button {
-                    if self.drag_state.is_some() {
-                        self.drag_state = None;
-                        return true;
-                    }
+                } else if *state == ElementState::Released
+                    && *button == self.drag_button
+                    && self.drag_state.is_some()
+                {
+                    self.drag_state = None;
+                    return true;
                 }
                 false
             }
@@ -466,7 +548,7 @@ impl CameraControl for MouseController {
 
             if let Some(current_pos) = input.mouse_position() {
                 if let Some(last) = state.last {
-                    let delta = current_pos - last;
+                    let delta = input.to_logical(current_pos - last);
                     state.last = Some(current_pos);
                     match state.mode {
                         DragMode::Orbit => pose.orbit_around_center(delta, self.orbit_sensitivity),
@@ -498,3 +580,536 @@ impl CameraControl for MouseController {
         self.drag_state = None;
     }
 }
+
+/// Touchscreen and trackpad gesture controller.
+///
+/// Controls:
+/// - One-finger drag: Orbit camera around the center point
+/// - Two-finger drag: Pan
+/// - Two-finger pinch / trackpad magnify: Zoom (proportional to distance to center)
+#[derive(Debug)]
+pub struct TouchController {
+    last_touches: BTreeMap<u64, Vec2>,
+    orbit_sensitivity: f32,
+    pan_sensitivity: f32,
+    pinch_sensitivity: f32,
+}
+
+impl TouchController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn orbit_sensitivity(&mut self, value: f32) -> &mut Self {
+        self.orbit_sensitivity = value;
+        self
+    }
+
+    pub fn pan_sensitivity(&mut self, value: f32) -> &mut Self {
+        self.pan_sensitivity = value;
+        self
+    }
+
+    pub fn pinch_sensitivity(&mut self, value: f32) -> &mut Self {
+        self.pinch_sensitivity = value;
+        self
+    }
+}
+
+impl Default for TouchController {
+    fn default() -> Self {
+        Self {
+            last_touches: BTreeMap::new(),
+            orbit_sensitivity: 0.005,
+            pan_sensitivity: 0.002,
+            pinch_sensitivity: 1.0,
+        }
+    }
+}
+
+impl CameraControl for TouchController {
+    fn update(
+        &mut self,
+        _dt: f32,
+        input: &InputState,
+        current: &CameraPose,
+    ) -> Option<CameraIntent> {
+        let mut pose = *current;
+        let touches = input.touches();
+
+        // Only move when the same fingers were down last frame, so adding or
+        // lifting a finger does not cause a jump.
+        let same_fingers = touches.len() == self.last_touches.len()
+            && touches.keys().all(|id| self.last_touches.contains_key(id));
+        if same_fingers {
+            let current_points: Vec<Vec2> = touches.values().copied().collect();
+            let last_points: Vec<Vec2> = self.last_touches.values().copied().collect();
+            match (current_points.as_slice(), last_points.as_slice()) {
+                ([current], [last]) => {
+                    let delta = input.to_logical(*current - *last);
+                    pose.orbit_around_center(delta, self.orbit_sensitivity);
+                }
+                ([a, b], [last_a, last_b]) => {
+                    let midpoint = (*a + *b) * 0.5;
+                    let last_midpoint = (*last_a + *last_b) * 0.5;
+                    let delta = input.to_logical(midpoint - last_midpoint);
+                    pose.pan(delta, self.pan_sensitivity);
+
+                    let spread = a.distance(*b);
+                    let last_spread = last_a.distance(*last_b);
+                    if spread > f32::EPSILON && last_spread > f32::EPSILON {
+                        let distance = (pose.position - pose.center).length();
+                        let ratio = (last_spread / spread - 1.0) * self.pinch_sensitivity;
+                        pose.zoom(distance * ratio);
+                    }
+                }
+                _ => {}
+            }
+        }
+        self.last_touches = touches.clone();
+
+        let pinch = input.pinch_delta();
+        if pinch != 0.0 {
+            let distance = (pose.position - pose.center).length();
+            pose.zoom(-distance * pinch * self.pinch_sensitivity);
+        }
+
+        if pose == *current {
+            None
+        } else {
+            Some(CameraIntent {
+                pose,
+                mode: IntentMode::Override,
+            })
+        }
+    }
+
+    fn on_reset(&mut self, _pose: &CameraPose) {
+        self.last_touches.clear();
+    }
+}
+
+/// Analog gamepad snapshot supplied by the embedder.
+///
+/// triad-window does not poll controllers itself; feed this from any backend
+/// (gilrs, SDL, a network bridge) through [`GamepadController::state`].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct GamepadState {
+    /// Movement: x strafes right, y moves forward. Each axis in -1..=1.
+    pub left_stick: Vec2,
+    /// Look: x turns right, y looks up. Each axis in -1..=1.
+    pub right_stick: Vec2,
+    /// Slows movement down, 0..=1.
+    pub left_trigger: f32,
+    /// Speeds movement up, 0..=1.
+    pub right_trigger: f32,
+}
+
+/// Fly-style camera controller driven by a shared [`GamepadState`].
+///
+/// Controls:
+/// - Left stick: Move forward/back and strafe (camera and center move together)
+/// - Right stick: Turn and look up/down around the camera position
+/// - Right trigger: Boost speed; left trigger: precision (slow) mode
+#[derive(Debug)]
+pub struct GamepadController {
+    state: Arc<Mutex<GamepadState>>,
+    dead_zone: f32,
+    move_speed: f32,
+    look_speed: f32,
+    boost: f32,
+}
+
+impl GamepadController {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shared state the embedder writes each frame.
+    pub fn state(&self) -> Arc<Mutex<GamepadState>> {
+        Arc::clone(&self.state)
+    }
+
+    /// Radial dead zone applied to both sticks, 0..1.
+    pub fn dead_zone(&mut self, value: f32) -> &mut Self {
+        self.dead_zone = value.clamp(0.0, 0.99);
+        self
+    }
+
+    /// World units per second at full stick deflection.
+    pub fn move_speed(&mut self, value: f32) -> &mut Self {
+        self.move_speed = value;
+        self
+    }
+
+    /// Radians per second at full stick deflection.
+    pub fn look_speed(&mut self, value: f32) -> &mut Self {
+        self.look_speed = value;
+        self
+    }
+
+    /// Speed multiplier at full right trigger.
+    pub fn boost(&mut self, value: f32) -> &mut Self {
+        self.boost = value;
+        self
+    }
+}
+
+impl Default for GamepadController {
+    fn default() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(GamepadState::default())),
+            dead_zone: 0.15,
+            move_speed: 2.0,
+            look_speed: 1.5,
+            boost: 4.0,
+        }
+    }
+}
+
+/// Zero the stick inside `dead_zone` and rescale the rest to 0..1.
+fn apply_dead_zone(stick: Vec2, dead_zone: f32) -> Vec2 {
+    let length = stick.length();
+    if length <= dead_zone {
+        return Vec2::ZERO;
+    }
+    let scaled = ((length - dead_zone) / (1.0 - dead_zone)).min(1.0);
+    stick * (scaled / length)
+}
+
+impl CameraControl for GamepadController {
+    fn update(
+        &mut self,
+        dt: f32,
+        _input: &InputState,
+        current: &CameraPose,
+    ) -> Option<CameraIntent> {
+        let state = *self.state.lock().ok()?;
+        let movement = apply_dead_zone(state.left_stick, self.dead_zone);
+        let look = apply_dead_zone(state.right_stick, self.dead_zone);
+        if movement == Vec2::ZERO && look == Vec2::ZERO {
+            return None;
+        }
+
+        let offset = current.center - current.position;
+        let distance = offset.length().max(0.1);
+        let mut forward = offset.try_normalize().unwrap_or(Vec3::NEG_Z);
+
+        let yaw = Quat::from_axis_angle(Vec3::Y, -look.x * self.look_speed * dt);
+        forward = yaw * forward;
+        let right = forward.cross(Vec3::Y).try_normalize().unwrap_or(Vec3::X);
+        let pitched = Quat::from_axis_angle(right, look.y * self.look_speed * dt) * forward;
+        // Stop short of straight up/down so the view basis stays defined.
+        if pitched.y.abs() < 0.99 {
+            forward = pitched;
+        }
+
+        let speed = self.move_speed
+            * (1.0 + state.right_trigger.clamp(0.0, 1.0) * (self.boost - 1.0))
+            * (1.0 - 0.75 * state.left_trigger.clamp(0.0, 1.0));
+        let position = current.position + (forward * movement.y + right * movement.x) * speed * dt;
+
+        Some(CameraIntent {
+            pose: CameraPose::new(position, position + forward * distance),
+            mode: IntentMode::Override,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use winit::dpi::PhysicalPosition;
+    use winit::event::{DeviceId, Touch};
+
+    fn touch(id: u64, phase: TouchPhase, x: f64, y: f64) -> WindowEvent {
+        WindowEvent::Touch(Touch {
+            device_id: DeviceId::dummy(),
+            phase,
+            location: PhysicalPosition::new(x, y),
+            force: None,
+            id,
+        })
+    }
+
+    fn pose() -> CameraPose {
+        CameraPose::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO)
+    }
+
+    /// Feed `events` as one frame and return the controller's intent pose.
+    fn frame(
+        controller: &mut TouchController,
+        input: &mut InputState,
+        current: &CameraPose,
+        events: &[WindowEvent],
+    ) -> Option<CameraPose> {
+        for event in events {
+            input.record_event(event);
+        }
+        let intent = controller.update(0.016, input, current);
+        input.end_frame();
+        intent.map(|intent| intent.pose)
+    }
+
+    #[test]
+    fn test_touch_one_finger_drag_orbits() {
+        let mut controller = TouchController::default();
+        let mut input = InputState::default();
+        let current = pose();
+        assert!(
+            frame(
+                &mut controller,
+                &mut input,
+                &current,
+                &[touch(1, TouchPhase::Started, 100.0, 100.0)]
+            )
+            .is_none()
+        );
+
+        let moved = frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[touch(1, TouchPhase::Moved, 140.0, 100.0)],
+        )
+        .expect("orbit");
+        assert_eq!(moved.center, current.center);
+        assert!((moved.yaw - (current.yaw - 40.0 * 0.005)).abs() < 1e-5);
+        let distance = (moved.position - moved.center).length();
+        assert!((distance - 5.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_touch_two_finger_drag_pans() {
+        let mut controller = TouchController::default();
+        let mut input = InputState::default();
+        let current = pose();
+        frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[
+                touch(1, TouchPhase::Started, 100.0, 100.0),
+                touch(2, TouchPhase::Started, 200.0, 100.0),
+            ],
+        );
+
+        let moved = frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[
+                touch(1, TouchPhase::Moved, 150.0, 100.0),
+                touch(2, TouchPhase::Moved, 250.0, 100.0),
+            ],
+        )
+        .expect("pan");
+        let shift = moved.center - current.center;
+        assert!(shift.x < 0.0);
+        assert!(shift.y.abs() < 1e-5);
+        assert!((moved.position - current.position - shift).length() < 1e-5);
+    }
+
+    #[test]
+    fn test_touch_pinch_zooms() {
+        let mut controller = TouchController::default();
+        let mut input = InputState::default();
+        let current = pose();
+        frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[
+                touch(1, TouchPhase::Started, 100.0, 100.0),
+                touch(2, TouchPhase::Started, 200.0, 100.0),
+            ],
+        );
+
+        // Spreading the fingers apart moves the camera closer to the center.
+        let zoomed = frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[
+                touch(1, TouchPhase::Moved, 50.0, 100.0),
+                touch(2, TouchPhase::Moved, 250.0, 100.0),
+            ],
+        )
+        .expect("pinch");
+        assert_eq!(zoomed.center, current.center);
+        let distance = (zoomed.position - zoomed.center).length();
+        assert!((distance - 2.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_touch_twist_does_not_move_camera() {
+        let mut controller = TouchController::default();
+        let mut input = InputState::default();
+        let current = pose();
+        frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[
+                touch(1, TouchPhase::Started, 100.0, 150.0),
+                touch(2, TouchPhase::Started, 200.0, 150.0),
+            ],
+        );
+
+        // Rotating both fingers about their midpoint keeps spread and midpoint.
+        let twisted = frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[
+                touch(1, TouchPhase::Moved, 150.0, 100.0),
+                touch(2, TouchPhase::Moved, 150.0, 200.0),
+            ],
+        );
+        assert!(twisted.is_none());
+    }
+
+    #[test]
+    fn test_touch_finger_change_does_not_jump() {
+        let mut controller = TouchController::default();
+        let mut input = InputState::default();
+        let current = pose();
+        frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[touch(1, TouchPhase::Started, 100.0, 100.0)],
+        );
+
+        let added = frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[
+                touch(1, TouchPhase::Moved, 120.0, 100.0),
+                touch(2, TouchPhase::Started, 300.0, 300.0),
+            ],
+        );
+        assert!(added.is_none());
+
+        let lifted = frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[touch(2, TouchPhase::Ended, 300.0, 300.0)],
+        );
+        assert!(lifted.is_none());
+    }
+
+    #[test]
+    fn test_trackpad_pinch_zooms() {
+        let mut controller = TouchController::default();
+        let mut input = InputState::default();
+        let current = pose();
+        let zoomed = frame(
+            &mut controller,
+            &mut input,
+            &current,
+            &[WindowEvent::PinchGesture {
+                device_id: DeviceId::dummy(),
+                delta: 0.5,
+                phase: TouchPhase::Moved,
+            }],
+        )
+        .expect("magnify");
+        let distance = (zoomed.position - zoomed.center).length();
+        assert!((distance - 2.5).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_single_active_keeps_mouse_over_touch() {
+        let mut controls = Controls::new();
+        controls.single_active(true);
+        let mut camera = Camera::new(Vec3::new(0.0, 0.0, 5.0), Vec3::ZERO);
+        let start = camera.pose();
+
+        controls.handle_event(&WindowEvent::CursorMoved {
+            device_id: DeviceId::dummy(),
+            position: PhysicalPosition::new(100.0, 100.0),
+        });
+        controls.handle_event(&WindowEvent::MouseInput {
+            device_id: DeviceId::dummy(),
+            state: ElementState::Pressed,
+            button: MouseButton::Left,
+        });
+        controls.update(0.016, &mut camera);
+        controls.handle_event(&WindowEvent::CursorMoved {
+            device_id: DeviceId::dummy(),
+            position: PhysicalPosition::new(140.0, 100.0),
+        });
+        controls.update(0.016, &mut camera);
+
+        assert_ne!(camera.pose(), start);
+    }
+
+    #[test]
+    fn test_dead_zone_boundary_and_rescale() {
+        assert_eq!(apply_dead_zone(Vec2::new(0.2, 0.0), 0.2), Vec2::ZERO);
+        assert_eq!(apply_dead_zone(Vec2::new(0.1, 0.1), 0.2), Vec2::ZERO);
+
+        let just_past = apply_dead_zone(Vec2::new(0.21, 0.0), 0.2);
+        assert!(just_past.x > 0.0 && just_past.x < 0.02);
+
+        let half = apply_dead_zone(Vec2::new(0.6, 0.0), 0.2);
+        assert!((half.x - 0.5).abs() < 1e-6);
+        let full = apply_dead_zone(Vec2::new(0.0, 1.0), 0.2);
+        assert!((full.y - 1.0).abs() < 1e-6);
+        let over = apply_dead_zone(Vec2::new(1.0, 1.0), 0.2);
+        assert!((over.length() - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_dead_zone_keeps_sign_of_negative_axes() {
+        let stick = apply_dead_zone(Vec2::new(-0.6, -0.3), 0.1);
+        assert!(stick.x < 0.0 && stick.y < 0.0);
+        assert!((stick.y / stick.x - 0.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_gamepad_speed_scales_with_stick_and_triggers() {
+        let mut controller = GamepadController::default();
+        controller.dead_zone(0.0).move_speed(2.0).boost(4.0);
+        let state = controller.state();
+        let input = InputState::default();
+        let current = pose();
+        let mut travel = |gamepad: GamepadState| {
+            *state.lock().unwrap() = gamepad;
+            let pose = controller
+                .update(1.0, &input, &current)
+                .expect("intent")
+                .pose;
+            pose.position - current.position
+        };
+
+        let full = travel(GamepadState {
+            left_stick: Vec2::new(0.0, 1.0),
+            ..Default::default()
+        });
+        assert!((full - Vec3::new(0.0, 0.0, -2.0)).length() < 1e-5);
+
+        let back = travel(GamepadState {
+            left_stick: Vec2::new(0.0, -0.5),
+            ..Default::default()
+        });
+        assert!((back - Vec3::new(0.0, 0.0, 1.0)).length() < 1e-5);
+
+        let boosted = travel(GamepadState {
+            left_stick: Vec2::new(0.0, 1.0),
+            right_trigger: 1.0,
+            ..Default::default()
+        });
+        assert!((boosted.length() - 8.0).abs() < 1e-4);
+
+        let precise = travel(GamepadState {
+            left_stick: Vec2::new(0.0, 1.0),
+            left_trigger: 1.0,
+            ..Default::default()
+        });
+        assert!((precise.length() - 0.5).abs() < 1e-5);
+    }
+}
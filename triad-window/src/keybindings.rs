@@ -0,0 +1,401 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use thiserror::Error;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+use crate::controls::InputState;
+
+/// Keys that can be named in a bindings file, matched by their `KeyCode` variant name.
+const NAMED_KEYS: &[KeyCode] = &[
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+    KeyCode::Digit0,
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+    KeyCode::F1,
+    KeyCode::F2,
+    KeyCode::F3,
+    KeyCode::F4,
+    KeyCode::F5,
+    KeyCode::F6,
+    KeyCode::F7,
+    KeyCode::F8,
+    KeyCode::F9,
+    KeyCode::F10,
+    KeyCode::F11,
+    KeyCode::F12,
+    KeyCode::Backquote,
+    KeyCode::Backslash,
+    KeyCode::BracketLeft,
+    KeyCode::BracketRight,
+    KeyCode::Comma,
+    KeyCode::Equal,
+    KeyCode::Minus,
+    KeyCode::Period,
+    KeyCode::Quote,
+    KeyCode::Semicolon,
+    KeyCode::Slash,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+    KeyCode::Backspace,
+    KeyCode::Enter,
+    KeyCode::Space,
+    KeyCode::Tab,
+    KeyCode::Escape,
+    KeyCode::Delete,
+    KeyCode::End,
+    KeyCode::Home,
+    KeyCode::Insert,
+    KeyCode::PageDown,
+    KeyCode::PageUp,
+    KeyCode::ArrowDown,
+    KeyCode::ArrowLeft,
+    KeyCode::ArrowRight,
+    KeyCode::ArrowUp,
+    KeyCode::Numpad0,
+    KeyCode::Numpad1,
+    KeyCode::Numpad2,
+    KeyCode::Numpad3,
+    KeyCode::Numpad4,
+    KeyCode::Numpad5,
+    KeyCode::Numpad6,
+    KeyCode::Numpad7,
+    KeyCode::Numpad8,
+    KeyCode::Numpad9,
+    KeyCode::NumpadAdd,
+    KeyCode::NumpadSubtract,
+    KeyCode::NumpadMultiply,
+    KeyCode::NumpadDivide,
+    KeyCode::NumpadDecimal,
+    KeyCode::NumpadEnter,
+    KeyCode::PrintScreen,
+    KeyCode::Pause,
+];
+
+/// Parse a key name such as `"KeyA"`, `"F1"` or `"BracketLeft"`.
+pub fn parse_key_code(name: &str) -> Option<KeyCode> {
+    NAMED_KEYS
+        .iter()
+        .copied()
+        .find(|key| format!("{key:?}") == name)
+}
+
+/// Display name of a key, in the same form [`parse_key_code`] accepts.
+pub fn key_name(key: KeyCode) -> String {
+    format!("{key:?}")
+}
+
+#[derive(Debug, Error)]
+pub enum KeyBindingsError {
+    #[error("failed to read key bindings: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("line {line}: {message}")]
+    Parse { line: usize, message: String },
+
+    #[error("line {line}: unknown key {name:?}")]
+    UnknownKey { line: usize, name: String },
+}
+
+/// Table of named actions to the keys that trigger them.
+///
+/// Actions are plain strings so embedders can add their own next to the
+/// window-level ones (`quit`, `toggle_ui`, `frame_scene`, `show_keybindings`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: BTreeMap<String, Vec<KeyCode>>,
+}
+
+impl KeyBindings {
+    pub const QUIT: &'static str = "quit";
+    pub const TOGGLE_UI: &'static str = "toggle_ui";
+    pub const FRAME_SCENE: &'static str = "frame_scene";
+    pub const SHOW_KEYBINDINGS: &'static str = "show_keybindings";
+
+    /// An empty table. Use [`KeyBindings::default`] for the window defaults.
+    pub fn empty() -> Self {
+        Self {
+            bindings: BTreeMap::new(),
+        }
+    }
+
+    /// Replace the keys bound to `action`. An empty list unbinds it.
+    pub fn bind(
+        &mut self,
+        action: impl Into<String>,
+        keys: impl IntoIterator<Item = KeyCode>,
+    ) -> &mut Self {
+        self.bindings
+            .insert(action.into(), keys.into_iter().collect());
+        self
+    }
+
+    pub fn with_binding(
+        mut self,
+        action: impl Into<String>,
+        keys: impl IntoIterator<Item = KeyCode>,
+    ) -> Self {
+        self.bind(action, keys);
+        self
+    }
+
+    /// Keys bound to `action`, empty if unbound.
+    pub fn keys(&self, action: &str) -> &[KeyCode] {
+        self.bindings.get(action).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn is_bound(&self, action: &str, key: KeyCode) -> bool {
+        self.keys(action).contains(&key)
+    }
+
+    /// First action (in name order) bound to `key`.
+    pub fn action_for(&self, key: KeyCode) -> Option<&str> {
+        self.bindings
+            .iter()
+            .find(|(_, keys)| keys.contains(&key))
+            .map(|(action, _)| action.as_str())
+    }
+
+    /// Whether any key bound to `action` was pressed this frame.
+    pub fn just_pressed(&self, action: &str, input: &InputState) -> bool {
+        self.keys(action)
+            .iter()
+            .any(|key| input.just_pressed(PhysicalKey::Code(*key)))
+    }
+
+    /// All bindings in action name order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &[KeyCode])> {
+        self.bindings
+            .iter()
+            .map(|(action, keys)| (action.as_str(), keys.as_slice()))
+    }
+
+    /// Overwrite bindings with every action present in `overrides`.
+    pub fn merge(&mut self, overrides: KeyBindings) -> &mut Self {
+        self.bindings.extend(overrides.bindings);
+        self
+    }
+
+    /// Parse a flat TOML table of `action = "Key"` or `action = ["Key", ...]`.
+    ///
+    /// An optional `[keybindings]` header is accepted; `#` starts a comment.
+    pub fn from_toml_str(source: &str) -> Result<Self, KeyBindingsError> {
+        let mut bindings = Self::empty();
+        for (index, raw) in source.lines().enumerate() {
+            let line = index + 1;
+            let text = strip_comment(raw).trim();
+            if text.is_empty() || text == "[keybindings]" {
+                continue;
+            }
+            let parse_error = |message: &str| KeyBindingsError::Parse {
+                line,
+                message: message.to_string(),
+            };
+            let (action, value) = text
+                .split_once('=')
+                .ok_or_else(|| parse_error("expected `action = keys`"))?;
+            let action = action.trim().trim_matches('"');
+            if action.is_empty() {
+                return Err(parse_error("missing action name"));
+            }
+
+            let value = value.trim();
+            let items = match value.strip_prefix('[') {
+                Some(list) => list
+                    .strip_suffix(']')
+                    .ok_or_else(|| parse_error("unterminated key list"))?,
+                None => value,
+            };
+            let mut keys = Vec::new();
+            for item in items.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+                let name = item
+                    .strip_prefix('"')
+                    .and_then(|s| s.strip_suffix('"'))
+                    .ok_or_else(|| parse_error("key names must be quoted strings"))?;
+                let key = parse_key_code(name).ok_or_else(|| KeyBindingsError::UnknownKey {
+                    line,
+                    name: name.to_string(),
+                })?;
+                keys.push(key);
+            }
+            bindings.bind(action, keys);
+        }
+        Ok(bindings)
+    }
+
+    /// Read a bindings file. Only the actions it lists are returned; merge
+    /// the result over [`KeyBindings::default`] to keep the remaining defaults.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, KeyBindingsError> {
+        let source = std::fs::read_to_string(path)?;
+        Self::from_toml_str(&source)
+    }
+}
+
+/// `line` up to the first `#` that is not inside a quoted string.
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    for (index, ch) in line.char_indices() {
+        match ch {
+            '"' => in_string = !in_string,
+            '#' if !in_string => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::empty()
+            .with_binding(Self::QUIT, [KeyCode::Escape])
+            .with_binding(Self::TOGGLE_UI, [KeyCode::F1])
+            .with_binding(Self::SHOW_KEYBINDINGS, [KeyCode::F2])
+            .with_binding(Self::FRAME_SCENE, [KeyCode::Home])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_key_code_round_trips_names() {
+        assert_eq!(parse_key_code("KeyA"), Some(KeyCode::KeyA));
+        assert_eq!(parse_key_code("F1"), Some(KeyCode::F1));
+        assert_eq!(parse_key_code("BracketLeft"), Some(KeyCode::BracketLeft));
+        assert_eq!(
+            parse_key_code(&key_name(KeyCode::Home)),
+            Some(KeyCode::Home)
+        );
+        assert_eq!(parse_key_code("keya"), None);
+        assert_eq!(parse_key_code("Hyper"), None);
+    }
+
+    #[test]
+    fn test_from_toml_str_scalar_and_list_values() {
+        let bindings = KeyBindings::from_toml_str(
+            r#"
+            quit = "KeyQ"
+            toggle_ui = ["F1", "KeyU"]
+            frame_scene = []
+            "#,
+        )
+        .expect("parse");
+        assert_eq!(bindings.keys(KeyBindings::QUIT), [KeyCode::KeyQ]);
+        assert_eq!(
+            bindings.keys(KeyBindings::TOGGLE_UI),
+            [KeyCode::F1, KeyCode::KeyU]
+        );
+        assert!(bindings.keys(KeyBindings::FRAME_SCENE).is_empty());
+        assert_eq!(bindings.iter().count(), 3);
+    }
+
+    #[test]
+    fn test_from_toml_str_header_and_comments() {
+        let bindings = KeyBindings::from_toml_str(
+            r#"
+            # Viewer overrides
+            [keybindings]
+            quit = "KeyQ" # trailing comment
+            "frame#scene" = "Home"
+            "#,
+        )
+        .expect("parse");
+        assert_eq!(bindings.keys(KeyBindings::QUIT), [KeyCode::KeyQ]);
+        assert_eq!(bindings.keys("frame#scene"), [KeyCode::Home]);
+        assert_eq!(bindings.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_strip_comment_ignores_hash_in_quotes() {
+        assert_eq!(strip_comment(r#"a = "KeyA" # note"#), r#"a = "KeyA" "#);
+        assert_eq!(strip_comment(r#""a#b" = "KeyA""#), r#""a#b" = "KeyA""#);
+        assert_eq!(strip_comment("# only a comment"), "");
+    }
+
+    #[test]
+    fn test_from_toml_str_keeps_unknown_actions() {
+        let bindings = KeyBindings::from_toml_str(r#"point_scale_up = "Equal""#).expect("parse");
+        assert_eq!(bindings.keys("point_scale_up"), [KeyCode::Equal]);
+    }
+
+    #[test]
+    fn test_from_toml_str_rejects_unknown_keys() {
+        let err = KeyBindings::from_toml_str("quit = \"KeyQ\"\ntoggle_ui = \"Hyper\"")
+            .expect_err("unknown key");
+        assert!(matches!(
+            err,
+            KeyBindingsError::UnknownKey { line: 2, ref name } if name == "Hyper"
+        ));
+    }
+
+    #[test]
+    fn test_from_toml_str_rejects_malformed_lines() {
+        let parse_line = |source: &str| match KeyBindings::from_toml_str(source) {
+            Err(KeyBindingsError::Parse { line, .. }) => Some(line),
+            _ => None,
+        };
+        assert_eq!(parse_line("quit = KeyQ"), Some(1));
+        assert_eq!(parse_line("quit = [\"KeyQ\", KeyW]"), Some(1));
+        assert_eq!(parse_line("\nquit = [\"KeyQ\""), Some(2));
+        assert_eq!(parse_line("quit"), Some(1));
+        assert_eq!(parse_line(" = \"KeyQ\""), Some(1));
+    }
+
+    #[test]
+    fn test_merge_overrides_only_present_actions() {
+        let mut bindings = KeyBindings::default();
+        let overrides = KeyBindings::from_toml_str(
+            r#"
+            quit = "KeyQ"
+            toggle_ui = []
+            "#,
+        )
+        .expect("parse");
+        bindings.merge(overrides);
+
+        assert_eq!(bindings.keys(KeyBindings::QUIT), [KeyCode::KeyQ]);
+        assert!(bindings.keys(KeyBindings::TOGGLE_UI).is_empty());
+        assert_eq!(bindings.keys(KeyBindings::FRAME_SCENE), [KeyCode::Home]);
+        assert_eq!(bindings.keys(KeyBindings::SHOW_KEYBINDINGS), [KeyCode::F2]);
+        assert_eq!(bindings.action_for(KeyCode::Escape), None);
+    }
+}
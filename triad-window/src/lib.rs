@@ -1,16 +1,21 @@
+mod annotations;
 mod app;
 mod camera;
 mod camera_uniforms;
 pub mod controls;
+mod keybindings;
 
 // Re-export types from triad-gpu
-// Note: RenderDelegate and SceneBounds have been removed
+// Note: RenderDelegate has been removed
 
-pub use app::{RendererManager, WindowConfig, egui, run_with_renderer_config};
-pub use camera::{Camera, CameraController, CameraPose, Projection};
+pub use annotations::{Annotation, project_to_viewport};
+pub use app::{RendererConfig, RendererManager, WindowConfig, egui, run_with_renderer_config};
+pub use camera::{Camera, CameraController, CameraPose, Projection, SceneBounds};
 pub use camera_uniforms::CameraUniforms;
 pub use controls::{
-    CameraControl, CameraIntent, Controls, FrameUpdate, InputState, IntentMode, MouseController,
+    CameraControl, CameraIntent, Controls, FrameUpdate, GamepadController, GamepadState,
+    InputState, IntentMode, MouseController, TouchController,
 };
+pub use keybindings::{KeyBindings, KeyBindingsError, key_name, parse_key_code};
 pub use winit::event::MouseButton;
 pub use winit::keyboard::{KeyCode, PhysicalKey};